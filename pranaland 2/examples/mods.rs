@@ -0,0 +1,201 @@
+//! Примеры модов к базовому набору содержимого: сначала загружается
+//! сломанный мод, чтобы показать сообщения об ошибках, затем рабочий.
+//!
+//! `cargo run --example mods`, язык - как у игры, в `PRANALAND_LANG`.
+
+use enum_map::enum_map;
+use pranaland::{
+    calendar::Season::*,
+    definitions::narrate,
+    fauna::{counts, Fauna, FaunaBehaviour, CHUCHUNDRA, SISYANDRA, SLESANDRA},
+    i18n::{set_locale, text, tr, Locale},
+    ids::{BiomeId, FaunaId, LocationId, NationId, OccupationId, ParamId, RaceId, ResourceId},
+    locations::{Biome, Location, LOCATIONS},
+    nations::{Nation, NATIONS},
+    occupations::{Occupation, Prerequisite, ZOOMBALING},
+    params::{Param::BalbesburgDamage, ParamDef},
+    registry::{self, base_pack, install, ContentPack, ContentRegistry},
+    resources::{amounts, HEALTH, SATISFACTION},
+    travel::{both_ways, RouteDef},
+    world::FaunaDynamics,
+};
+
+fn main() {
+    if let Ok(lang) = std::env::var("PRANALAND_LANG") {
+        match lang.parse::<Locale>() {
+            Ok(locale) => set_locale(locale),
+            Err(_) => eprintln!("Unknown PRANALAND_LANG={lang}, expected \"ru\" or \"en\"."),
+        }
+    }
+
+    match ContentRegistry::load(base_pack(), vec![broken_pack()]) {
+        Ok(_) => println!("{}", text("report.content.broken_loaded")),
+        Err(errors) => println!("{}", tr!("report.content.broken", errors = errors)),
+    }
+
+    let registry = match ContentRegistry::load(base_pack(), vec![cringeland_pack()]) {
+        Ok(registry) => registry,
+        Err(error) => exit(error),
+    };
+    if install(registry).is_err() {
+        exit(text("report.content.installed"));
+    }
+
+    let packs = match registry::content() {
+        Ok(content) => content.packs.join(", "),
+        Err(error) => exit(error),
+    };
+    println!("{}", tr!("report.content.packs", packs = packs));
+    println!("\n{}", text("report.content.locations"));
+    for location in LOCATIONS.iter() {
+        println!("{location}\n");
+    }
+    println!("{}", text("report.content.nations"));
+    for nation in NATIONS.iter() {
+        println!("- {} ({})", nation.name_plural(), nation.race.name());
+    }
+}
+
+/// Сообщает об ошибке и завершает программу.
+fn exit(error: impl std::fmt::Display) -> ! {
+    eprintln!("{}", tr!("report.error", error = error));
+    std::process::exit(1)
+}
+
+const CRINGELAND: BiomeId = BiomeId("cringeland");
+const KRINZHEPOL: LocationId = LocationId("krinzhepol");
+const KRINZHANDRA: FaunaId = FaunaId("krinzhandra");
+const ZOOMERY_ZOOMBALING: ParamId = ParamId("zoomery_zoombaling");
+
+/// Сообщения Кринжленда. Названия по-русски берутся из самого набора.
+const CRINGELAND_RU: &str = "
+zoomery.zoombaling = {name} - {nation}, и зумбальство даётся особенно тяжело.
+";
+
+const CRINGELAND_EN: &str = "
+fauna.krinzhandra.name = Krinzhandras
+fauna.krinzhandra.one = Krinzhandra
+occupation.cringing.name = Cringing
+occupation.cringing.requirement = only in Cringeland
+biome.cringeland.name = Cringeland
+location.krinzhepol.name = Cringeville
+location.krinzhepol.effect = Nothing special.
+nation.zoomery.plural = Zoomers
+nation.zoomery.singular = Zoomer
+param.zoomery_zoombaling = Zoomers: satisfaction multiplier when zoombaling
+zoomery.zoombaling = {name} ({nation}) finds zoombaling especially hard.
+";
+
+/// Новый биом с одной локацией, связанной с Долбесбургом, новая нация,
+/// новый вид фауны, занятие, которому можно предаваться только в новом биоме,
+/// и параметр правил для эффекта новой нации.
+fn cringeland_pack() -> ContentPack {
+    ContentPack {
+        name: "Кринжленд",
+        resources: vec![],
+        occupations: vec![Occupation {
+            id: OccupationId("cringing"),
+            name: "Кринжевать",
+            consumes: amounts(&[(HEALTH, 1.0)]),
+            produces: SATISFACTION,
+            fauna: vec![(KRINZHANDRA, 1.5)],
+            prerequisite: Some(Prerequisite {
+                description: "только в Кринжленде",
+                check: &|_, location| location.biome == CRINGELAND,
+            }),
+        }],
+        races: vec![],
+        nations: vec![Nation {
+            id: NationId("zoomery"),
+            name_plural: "Зумеры",
+            name_singular: "Зумер",
+            race: RaceId("hipstick"),
+            pre_effect: None,
+            post_effect: Some(&|state, action| {
+                // Зумбалить не любят и тратят на это больше удовлетворённости.
+                if action.occupation == Some(ZOOMBALING) {
+                    narrate!(
+                        state,
+                        "zoomery.zoombaling",
+                        name = state.player.name,
+                        nation = state.player.nationality.name_singular()
+                    );
+                    state.resource_change[SATISFACTION] *= state.param(ZOOMERY_ZOOMBALING)?;
+                }
+                Ok(())
+            }),
+        }],
+        biomes: vec![Biome {
+            id: CRINGELAND,
+            name: "Кринжленд",
+            productivity: enum_map! { Spring => 1.0, Summer => 1.0, Autumn => 1.0, Winter => 1.0 },
+        }],
+        locations: vec![Location {
+            id: KRINZHEPOL,
+            name: "Кринжеполь",
+            biome: CRINGELAND,
+            fauna: counts(&[
+                (SLESANDRA, 2),
+                (SISYANDRA, 2),
+                (CHUCHUNDRA, 2),
+                (KRINZHANDRA, 3),
+            ]),
+            effect_description: "Ничего особенного.",
+            pre_effect: None,
+            post_effect: None,
+            fauna_behaviours: vec![],
+            dynamics: Some(FaunaDynamics::STANDARD),
+        }],
+        routes: both_ways(
+            LocationId("dolbesburg"),
+            KRINZHEPOL,
+            amounts(&[(SATISFACTION, 1.0)]),
+            0,
+        )
+        .into(),
+        fauna: vec![Fauna {
+            id: KRINZHANDRA,
+            name: "Кринжандры",
+            name_singular: "Кринжандра",
+            behaviours: vec![],
+        }],
+        events: vec![],
+        params: vec![ParamDef {
+            id: ZOOMERY_ZOOMBALING,
+            name: "Зумеры: множитель трат удовлетворённости при зумбальстве",
+            default: 1.2,
+            probability: false,
+        }],
+        messages: vec![(Locale::Ru, CRINGELAND_RU), (Locale::En, CRINGELAND_EN)],
+    }
+}
+
+/// Мод с ошибками - чтобы показать, что загрузка сообщает обо всех сразу.
+fn broken_pack() -> ContentPack {
+    let mut pack = cringeland_pack();
+    pack.name = "Сломанный мод";
+    pack.nations[0].race = RaceId("boomer");
+    pack.locations[0].biome = BiomeId("nowhere");
+    pack.locations[0].dynamics = Some(FaunaDynamics {
+        capacity: 2.0,
+        migration: 1.5,
+        ..pack.locations[0].dynamics.unwrap()
+    });
+    pack.fauna[0].behaviours.push(FaunaBehaviour::Aggressive {
+        resource: HEALTH,
+        chance: BalbesburgDamage,
+        damage: BalbesburgDamage,
+    });
+    pack.routes[0].cost = amounts(&[(SATISFACTION, -1.0)]);
+    pack.params[0].default = -1.2;
+    pack.routes.push(RouteDef {
+        from: KRINZHEPOL,
+        to: LocationId("atlantis"),
+        cost: amounts(&[(ResourceId("karma"), 1.0)]),
+        extra_intervals: 0,
+    });
+    pack.biomes.extend(cringeland_pack().biomes);
+    pack.messages
+        .push((Locale::En, "zoomery.zoombaling: {name} is cringing"));
+    pack
+}
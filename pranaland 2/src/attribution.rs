@@ -0,0 +1,81 @@
+use crate::{
+    batch::GameOutcome,
+    definitions::{EffectSource, ResourceMap},
+    ids::{IdMap, ResourceId},
+    resources::RESOURCES,
+};
+use std::collections::HashMap;
+
+/// Какие эффекты приводят Игроков к смерти.
+pub struct DeathAttribution {
+    /// Количество смертей от нехватки каждого ресурса.
+    pub deaths: IdMap<ResourceId, usize>,
+
+    /// Доля каждого источника в суммарных потерях ресурса у Игроков,
+    /// погибших от нехватки именно этого ресурса.
+    pub loss_share: HashMap<EffectSource, ResourceMap>,
+
+    /// Сколько смертей от нехватки каждого ресурса не случилось бы на последнем
+    /// временном интервале без вклада источника.
+    pub fatal: HashMap<EffectSource, IdMap<ResourceId, usize>>,
+}
+
+impl DeathAttribution {
+    pub fn new(outcomes: &[GameOutcome]) -> Self {
+        let mut deaths: IdMap<ResourceId, usize> = IdMap::default();
+        let mut losses: HashMap<EffectSource, ResourceMap> = HashMap::new();
+        let mut fatal: HashMap<EffectSource, IdMap<ResourceId, usize>> = HashMap::new();
+
+        for outcome in outcomes {
+            let Some(cause) = outcome.death_cause else {
+                continue;
+            };
+            deaths[cause] += 1;
+
+            for (&source, loss) in &outcome.losses {
+                losses.entry(source).or_default()[cause] += -loss[cause];
+            }
+            for &source in &outcome.fatal_effects {
+                fatal.entry(source).or_default()[cause] += 1;
+            }
+        }
+
+        let mut total = ResourceMap::default();
+        for loss in losses.values() {
+            for (res, amount) in loss {
+                total[res] += amount;
+            }
+        }
+
+        let loss_share = losses
+            .into_iter()
+            .map(|(source, loss)| {
+                let share = RESOURCES
+                    .iter()
+                    .map(|res| {
+                        let share = if total[res.id] > 0.0 {
+                            loss[res.id] / total[res.id]
+                        } else {
+                            0.0
+                        };
+                        (res.id, share)
+                    })
+                    .collect();
+                (source, share)
+            })
+            .collect();
+
+        Self {
+            deaths,
+            loss_share,
+            fatal,
+        }
+    }
+
+    /// Источники изменений, упорядоченные по названию.
+    pub fn sources(&self) -> Vec<EffectSource> {
+        let mut sources: Vec<EffectSource> = self.loss_share.keys().copied().collect();
+        sources.sort_by_key(|source| source.to_string());
+        sources
+    }
+}
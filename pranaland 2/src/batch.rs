@@ -0,0 +1,216 @@
+use crate::{
+    calendar::Calendar,
+    definitions::{EffectSource, GameState, ResourceMap, SimError, Strategy},
+    ids::ResourceId,
+    nations::Nation,
+    params::{default_params, ParamMap},
+    world::FaunaDynamics,
+};
+use std::collections::HashMap;
+
+/// Параметры пакетного прогона симуляций.
+#[derive(Debug, Clone, Copy)]
+pub struct BatchConfig {
+    /// Количество симуляций в пакете.
+    pub games: usize,
+
+    /// Максимальное количество временных интервалов в одной симуляции.
+    /// Игрок, доживший до этого момента, считается выжившим.
+    pub max_steps: usize,
+
+    /// Зерно первой симуляции. Симуляция №i использует зерно `seed + i`
+    /// (по модулю 2^64), поэтому разные стратегии проверяются на одних
+    /// и тех же случайностях.
+    pub seed: u64,
+
+    /// Числовые параметры правил симуляции.
+    pub params: ParamMap,
+
+    /// Проверять ли инварианты состояния после каждого интервала.
+    /// Игры с нарушениями попадают в `BatchResult::errors`.
+    pub check_invariants: bool,
+
+    /// Динамика фауны для локаций, где содержимое её не задаёт
+    /// (см. `WorldState::default_dynamics`). `None` - фауна неизменна.
+    pub fauna_dynamics: Option<FaunaDynamics>,
+
+    /// Включён ли календарь (см. `WorldState::calendar`).
+    pub calendar: bool,
+}
+
+/// Итог одной симуляции.
+#[derive(Clone)]
+pub struct GameOutcome {
+    /// Нация Игрока в этой симуляции.
+    pub nation: &'static Nation,
+
+    /// Сколько временных интервалов прожил Игрок.
+    pub steps_survived: usize,
+
+    /// Дожил ли Игрок до `BatchConfig::max_steps`.
+    pub survived: bool,
+
+    /// Самый маленький из ресурсов Игрока после каждого временного интервала.
+    pub min_resource_trace: Vec<f64>,
+
+    /// Ресурс, закончившийся у Игрока, если он погиб.
+    pub death_cause: Option<ResourceId>,
+
+    /// Суммарные потери каждого ресурса за всю симуляцию (отрицательные числа)
+    /// по источникам изменений.
+    pub losses: HashMap<EffectSource, ResourceMap>,
+
+    /// Эффекты, без вклада которых Игрок пережил бы последний временной интервал.
+    pub fatal_effects: Vec<EffectSource>,
+
+    /// Прервана ли симуляция ошибкой (сама ошибка - в `BatchResult::errors`).
+    /// Игрок тогда считается погибшим на интервале ошибки, а `death_cause`
+    /// пуст.
+    pub failed: bool,
+}
+
+/// Результаты пакетного прогона.
+pub struct BatchResult {
+    /// Итоги симуляций, в том числе прерванных ошибкой
+    /// (`GameOutcome::failed`).
+    pub outcomes: Vec<GameOutcome>,
+
+    /// Ошибки симуляций, прерванных из-за стратегии или правил. Если
+    /// симуляция не смогла даже начаться, её итога в `outcomes` нет.
+    pub errors: Vec<SimError>,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            games: 100,
+            max_steps: 100,
+            seed: 0,
+            params: default_params(),
+            check_invariants: false,
+            fauna_dynamics: None,
+            calendar: false,
+        }
+    }
+}
+
+/// Прогоняет одну симуляцию с зерном `seed` без вывода в STDOUT.
+pub fn run_game(
+    strategy: &dyn Strategy,
+    nation: Option<&'static Nation>,
+    seed: u64,
+    config: &BatchConfig,
+) -> Result<GameOutcome, SimError> {
+    match play(strategy, nation, seed, config)? {
+        (outcome, None) => Ok(outcome),
+        (_, Some(error)) => Err(error),
+    }
+}
+
+/// Прогоняет одну симуляцию. Если её прервала ошибка, возвращает итог
+/// игры до ошибки (`GameOutcome::failed`) вместе с ошибкой, а если игра
+/// не смогла начаться - только ошибку.
+fn play(
+    strategy: &dyn Strategy,
+    nation: Option<&'static Nation>,
+    seed: u64,
+    config: &BatchConfig,
+) -> Result<(GameOutcome, Option<SimError>), SimError> {
+    let max_steps = config.max_steps;
+    let mut state = GameState::new("Игрок".to_string(), nation, seed)?;
+    state.verbose = false;
+    state.params = config.params;
+    state.check_invariants = config.check_invariants;
+    state.world.default_dynamics = config.fauna_dynamics;
+    state.world.calendar = config.calendar.then(Calendar::default);
+
+    let mut min_resource_trace = Vec::with_capacity(max_steps);
+    let mut losses: HashMap<EffectSource, ResourceMap> = HashMap::new();
+    let mut fatal_effects = Vec::new();
+    let mut error = None;
+
+    while state.player.is_alive() && state.time_passed < max_steps {
+        if let Err(failure) = state.advance(strategy) {
+            error = Some(failure);
+            break;
+        }
+        min_resource_trace.push(state.player.min_resource());
+
+        for contribution in &state.attribution {
+            let loss = losses.entry(contribution.source).or_default();
+            for (res, delta) in contribution.delta() {
+                loss[res] += delta.min(0.0);
+            }
+        }
+
+        if let Some(cause) = state.player.death_cause() {
+            fatal_effects = state
+                .attribution
+                .iter()
+                .filter(|c| {
+                    let delta = c.delta()[cause];
+                    delta < 0.0 && state.player.margin(cause) - delta > 0.0
+                })
+                .map(|c| c.source)
+                .collect();
+        }
+    }
+
+    let failed = error.is_some();
+    let outcome = GameOutcome {
+        nation: state.player.nationality,
+        steps_survived: state.time_passed,
+        survived: !failed && state.player.is_alive(),
+        min_resource_trace,
+        death_cause: state.player.death_cause().filter(|_| !failed),
+        losses,
+        fatal_effects,
+        failed,
+    };
+    Ok((outcome, error))
+}
+
+/// Прогоняет `config.games` симуляций со стратегией `strategy`.
+/// Если `nation == None`, нация каждого Игрока выбирается случайно.
+/// Симуляция, прерванная ошибкой, не останавливает остальные и считается
+/// гибелью Игрока на интервале ошибки.
+pub fn run_batch(
+    strategy: &dyn Strategy,
+    nation: Option<&'static Nation>,
+    config: &BatchConfig,
+) -> BatchResult {
+    let mut result = BatchResult {
+        outcomes: Vec::with_capacity(config.games),
+        errors: Vec::new(),
+    };
+    for i in 0..config.games as u64 {
+        match play(strategy, nation, config.seed.wrapping_add(i), config) {
+            Ok((outcome, error)) => {
+                result.outcomes.push(outcome);
+                result.errors.extend(error);
+            }
+            Err(error) => result.errors.push(error),
+        }
+    }
+    result
+}
+
+impl BatchResult {
+    /// Среднее количество прожитых временных интервалов.
+    pub fn mean_survival(&self) -> f64 {
+        if self.outcomes.is_empty() {
+            return 0.0;
+        }
+        let total: usize = self.outcomes.iter().map(|o| o.steps_survived).sum();
+        total as f64 / self.outcomes.len() as f64
+    }
+
+    /// Доля Игроков, доживших до конца симуляции.
+    pub fn survival_rate(&self) -> f64 {
+        if self.outcomes.is_empty() {
+            return 0.0;
+        }
+        let survived = self.outcomes.iter().filter(|o| o.survived).count();
+        survived as f64 / self.outcomes.len() as f64
+    }
+}
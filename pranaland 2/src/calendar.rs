@@ -0,0 +1,185 @@
+use crate::{
+    definitions::{ResourceMap, SimError, SimErrorKind},
+    i18n::{localized_display, text_or, tr},
+    ids::{BiomeId, EventId, IdMap, ResourceId},
+    registry::{content, ContentKind},
+    resources::{amounts, HEALTH, MONEY, SATISFACTION},
+};
+use lazy_static::lazy_static;
+use rand::Rng;
+use std::fmt;
+use strum::IntoEnumIterator;
+
+/// Сколько временных интервалов длится каждое время года.
+pub const SEASON_LENGTH: usize = 10;
+
+/// Время года. Времена года сменяют друг друга по кругу, начиная с весны.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_map::Enum, strum::EnumIter)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+localized_display!(Season => "season");
+
+/// Глобальное событие, на несколько интервалов меняющее жизнь во всём мире.
+pub struct GlobalEvent {
+    pub id: EventId,
+
+    /// Человекочитаемое название события, например "Эпидемия".
+    /// Перевод - в каталоге сообщений по ключу `event.<id>.name`.
+    pub name: &'static str,
+
+    /// Вероятность того, что событие начнётся на очередном временном интервале
+    /// (если оно ещё не идёт).
+    pub chance: f64,
+
+    /// Сколько временных интервалов длится событие.
+    pub duration: usize,
+
+    /// Множитель дохода от фауны для занятий, приносящих каждый ресурс
+    /// (для неуказанных ресурсов - 1.0).
+    pub income: ResourceMap,
+
+    /// Сколько каждого ресурса Игрок теряет за каждый интервал события,
+    /// чем бы он ни занимался.
+    pub upkeep: ResourceMap,
+}
+
+lazy_static! {
+    /// Все глобальные события из установленного содержимого игры (`content()`).
+    pub static ref EVENTS: &'static [GlobalEvent] =
+        content().map(|content| content.events.as_slice()).unwrap_or_default();
+}
+
+/// Глобальные события базового набора содержимого.
+pub fn base_events() -> Vec<GlobalEvent> {
+    vec![
+        GlobalEvent {
+            id: EventId("epidemic"),
+            name: "Эпидемия",
+            chance: 0.02,
+            duration: 5,
+            income: factors(&[(HEALTH, 0.5)]),
+            upkeep: amounts(&[(HEALTH, 0.3)]),
+        },
+        GlobalEvent {
+            id: EventId("market_crash"),
+            name: "Биржевой крах",
+            chance: 0.02,
+            duration: 5,
+            income: factors(&[(MONEY, 0.5)]),
+            upkeep: ResourceMap::default(),
+        },
+        GlobalEvent {
+            id: EventId("festival"),
+            name: "Фестиваль",
+            chance: 0.03,
+            duration: 3,
+            income: factors(&[(SATISFACTION, 1.5)]),
+            upkeep: amounts(&[(MONEY, 0.2)]),
+        },
+    ]
+}
+
+/// Множители дохода с указанными значениями (остальные - единицы).
+fn factors(pairs: &[(ResourceId, f64)]) -> ResourceMap {
+    let mut map = ResourceMap::filled(1.0);
+    for &(resource, factor) in pairs {
+        map[resource] = factor;
+    }
+    map
+}
+
+impl Season {
+    /// Время года на временном интервале `time`.
+    pub fn at(time: usize) -> Self {
+        let seasons: Vec<Season> = Season::iter().collect();
+        seasons[(time / SEASON_LENGTH) % seasons.len()]
+    }
+}
+
+impl GlobalEvent {
+    /// Название события на текущем языке.
+    pub fn name(&self) -> &'static str {
+        text_or(&format!("event.{}.name", self.id), self.name)
+    }
+}
+
+impl EventId {
+    pub fn try_get(self) -> Result<&'static GlobalEvent, SimError> {
+        EVENTS.iter().find(|event| event.id == self).ok_or_else(|| {
+            SimError::new(SimErrorKind::UnknownId {
+                kind: ContentKind::Event,
+                id: self.to_string(),
+            })
+        })
+    }
+
+    /// Название события на текущем языке, а если такого события нет
+    /// в содержимом игры - его идентификатор.
+    pub fn name(self) -> &'static str {
+        self.try_get().map_or(self.0, GlobalEvent::name)
+    }
+}
+
+/// Мировые часы: время года и идущие события.
+#[derive(Debug, Clone, Default)]
+pub struct Calendar {
+    /// Количество временных интервалов, прошедших с сотворения мира.
+    pub time: usize,
+
+    /// Сколько интервалов ещё продлится каждое идущее событие.
+    pub events: IdMap<EventId, usize>,
+}
+
+impl Calendar {
+    pub fn season(&self) -> Season {
+        Season::at(self.time)
+    }
+
+    /// События, идущие на текущем временном интервале.
+    pub fn active_events(&self) -> impl Iterator<Item = &'static GlobalEvent> + '_ {
+        EVENTS.iter().filter(|event| self.events[event.id] > 0)
+    }
+
+    /// Множитель дохода от фауны для занятия, приносящего `resource`, в биоме `biome`.
+    pub fn income_factor(&self, biome: BiomeId, resource: ResourceId) -> f64 {
+        self.active_events()
+            .map(|event| event.income[resource])
+            .fold(
+                biome.try_get().map_or(1.0, |biome| biome.productivity[self.season()]),
+                |factor, x| factor * x,
+            )
+    }
+
+    /// Переход к следующему временному интервалу: идущие события
+    /// приближаются к концу, а новые могут начаться.
+    pub fn step(&mut self, rng: &mut impl Rng) {
+        self.time += 1;
+        for event in EVENTS.iter() {
+            let remaining = &mut self.events[event.id];
+            if *remaining > 0 {
+                *remaining -= 1;
+            } else if rng.gen::<f64>() <= event.chance {
+                *remaining = event.duration;
+            }
+        }
+    }
+}
+
+impl fmt::Display for Calendar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.season())?;
+        for event in self.active_events() {
+            write!(
+                f,
+                "{}",
+                tr!("calendar.event", event = event.name(), n = self.events[event.id])
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -1,11 +1,17 @@
-use crate::{locations::Location, nations::Nation};
-use enum_map::EnumMap;
+use crate::{
+    i18n::{text, tr},
+    ids::{EventId, FaunaId, IdMap, LocationId, NationId, OccupationId, ParamId, ResourceId},
+    invariants::InvariantReport,
+    locations::Location,
+    nations::Nation,
+    params::{ContentParamMap, Param, ParamMap},
+    registry::{ContentErrors, ContentKind},
+    resources::{starting_resources, RESOURCES},
+    trade::{MarketView, TradeAction},
+    world::WorldState,
+};
 use rand_chacha::ChaCha8Rng;
 use std::fmt;
-use strum::IntoEnumIterator;
-
-/// Изначальное количество каждого ресурса при создании нового Игрока.
-pub const STARTING_RESOURCE_AMOUNT: f64 = 10.0;
 
 /// "Представитель разумной расы", он же Игрок.
 #[derive(Clone)]
@@ -23,44 +29,23 @@ pub struct Player {
     pub resources: ResourceMap,
 }
 
-/// Главные жизненные ресурсы игрока - здоровье, деньги, удовлетворённость жизнью.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_map::Enum, strum::EnumIter, strum::Display)]
-pub enum Resource {
-    #[strum(to_string = "Здоровье")]
-    Health,
-
-    #[strum(to_string = "Деньги")]
-    Money,
-
-    #[strum(to_string = "Удовл. жизнью")]
-    Satisfaction,
-}
+/// Отображение из ресурса в его количество. Ресурсы описаны
+/// в содержимом игры (`RESOURCES`); тех, что не записаны, - ноль.
+pub type ResourceMap = IdMap<ResourceId, f64>;
 
-/// Отображение из типа ресурса в его количество.
-pub type ResourceMap = EnumMap<Resource, f64>;
-
-/// Типы фауны.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display, enum_map::Enum)]
-pub enum Fauna {
-    #[strum(to_string = "Слесандры")]
-    Slesandra,
-
-    #[strum(to_string = "Сисяндры")]
-    Sisyandra,
-
-    #[strum(to_string = "Чучундры")]
-    Chuchundra,
-}
-
-/// Количество представителей для каждого типа фауны.
-pub type FaunaMap = EnumMap<Fauna, usize>;
+/// Количество представителей для каждого вида фауны. Виды описаны
+/// в содержимом игры (`FAUNA`); тех, что не записаны, - ноль.
+pub type FaunaMap = IdMap<FaunaId, usize>;
 
 /// Эффект, применяемый на каждом временном интервале после перехода
 /// в новую локацию но **ДО** того как Игрок предастся какому-либо занятию.
 ///
 /// Параметры:
-/// - текущее состояние симуляции (его можно изменять);
-pub type PreEffect = dyn Fn(&mut GameState) + Sync + 'static;
+/// - текущее состояние симуляции (его можно изменять).
+///
+/// Если состояние не укладывается в правила эффекта, он возвращает ошибку
+/// (см. `GameState::error`), и временной интервал прерывается.
+pub type PreEffect = dyn Fn(&mut GameState) -> Result<(), SimError> + Sync + 'static;
 
 /// Эффект, применяемый на каждом временном интервале после перехода
 /// в новую локацию **ПОСЛЕ** того как Игрок предастся какому-либо занятию.
@@ -69,18 +54,24 @@ pub type PreEffect = dyn Fn(&mut GameState) + Sync + 'static;
 /// Параметры:
 /// - текущее состояние симуляции (его можно изменять);
 /// - действие Игрока на этом временном интервале.
-pub type PostEffect = dyn Fn(&mut GameState, &Action) + Sync + 'static;
+///
+/// Ошибки - как у `PreEffect`.
+pub type PostEffect = dyn Fn(&mut GameState, &Action) -> Result<(), SimError> + Sync + 'static;
 
 /// Максимальная длина `GameState::location_history`.
 pub const MAX_LOCATION_HISTORY_LEN: usize = 10;
 
 /// Состояние симуляции (для краткости мы назовём её "игрой").
+#[derive(Clone)]
 pub struct GameState {
     /// Генератор псевдослучайных чисел.
     /// Алгоритм генерации следующего числа полностью детерминирован, 
     /// поэтому мы можем воспроизводить симуляции по зерну (seed).
     pub rng: Box<ChaCha8Rng>,
 
+    /// Зерно, с которым была начата игра.
+    pub seed: u64,
+
     /// Игрок - имя, нация, количество ресурсов.
     pub player: Player,
 
@@ -100,14 +91,34 @@ pub struct GameState {
 
     /// Количество представителей всех типов фауны после прменения
     /// эффекта локации.
-    /// В большинстве случаев сюда просто клонируется `location.fauna`
-    /// после перехода в новую локацию.
+    /// После перехода в новую локацию сюда записывается текущая численность
+    /// фауны в ней из `world`, пока Игрок остаётся на месте - она может
+    /// только уменьшаться.
     pub effective_fauna: FaunaMap,
 
+    /// На сколько эффекты увеличили `effective_fauna` на текущем временном
+    /// интервале (см. `GameState::declare_fauna`).
+    pub declared_fauna: FaunaMap,
+
+    /// Численность фауны во всех локациях, сохраняющаяся между посещениями.
+    pub world: WorldState,
+
+    /// Переход в другой биом, если Игрок сейчас в пути.
+    pub transit: Option<Transit>,
+
+    /// Сколько Игроков (включая этого) предаются на текущем интервале тому же
+    /// занятию в той же локации. Доход от фауны делится между ними поровну.
+    pub crowding: usize,
+
+    /// Обновлять ли `world` в конце каждого временного интервала.
+    /// Когда мир общий для нескольких Игроков, его обновляет `World`,
+    /// один раз за интервал.
+    pub steps_world: bool,
+
     /// Изменение количества ресурсов Игрока в конце текущего временного интервала.
     /// В начале временного интервала сюда записываются нули (`ResourceMap::default()`).
     /// После того как Игрок выберет себе занятие, в этот асоциативный массив записываются
-    /// стандартные изменение (-1 ХП, -1 ед. денег, +2 ед. удовольствия * effective_fauna[Sisyandra]).
+    /// стандартные изменение (-1 ХП, -1 ед. денег, +2 ед. удовольствия * effective_fauna[SISYANDRA]).
     /// Пост-эффекты локации и национальности Игрока могут изменять это поле.
     /// После применения пост-эффектов значения из этого поля добавляются к `player.resources`,
     /// а само поле обнуляется.
@@ -118,35 +129,235 @@ pub struct GameState {
     /// Количество прошедших временных интервалов с начала игры.
     /// Для первого временного интервала здесь будет записано значение `0`.
     pub time_passed: usize,
+
+    /// Вклад каждого эффекта в `resource_change` на текущем временном интервале
+    /// в порядке применения эффектов.
+    pub attribution: Vec<EffectContribution>,
+
+    /// Числовые параметры правил, которыми пользуются эффекты.
+    pub params: ParamMap,
+
+    /// Значения параметров правил, объявленных содержимым игры (`ParamDef`).
+    pub content_params: ContentParamMap,
+
+    /// Выводить ли в STDOUT описание происходящего.
+    /// При пакетном прогоне тысяч симуляций это поле выставляется в `false`.
+    pub verbose: bool,
+
+    /// Журнал событий - те же сообщения, что выводятся при `verbose`, но
+    /// независимо от него. Ведётся, только если не `None`.
+    pub events: Option<Vec<String>>,
+
+    /// Проверять ли целостность состояния после каждого временного интервала
+    /// (см. модуль `invariants`). Проверка замедляет симуляцию, поэтому
+    /// по умолчанию выключена.
+    pub check_invariants: bool,
 }
 
-/// Занятие, которому Игрок может предаваться в конце каждого временного
-/// интервала.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::EnumIter, strum::Display)]
-pub enum Occupation {
-    /// Зумбалить - обменивать здоровье и радость на деньги.
-    #[strum(to_string = "Зумбалить")]
-    Zoombaling,
+/// Источник изменения ресурсов Игрока.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EffectSource {
+    /// Стандартные затраты и доход от занятия (или от безделья).
+    Occupation,
 
-    /// Гульбонить - обменивать деньги и здоровье на удовольствия.
-    #[strum(to_string = "Гульбонить")]
-    Goolboning,
+    /// Эффект локации.
+    Location(LocationId),
 
-    /// Шлямсать - отдавать деньги и лишать себя удовольствий ради здоровья.
-    #[strum(to_string = "Шлямсить")]
-    Shlamsing,
+    /// Эффект нации.
+    Nation(NationId),
+
+    /// Стоимость дороги и безделье в пути.
+    Travel,
+
+    /// Естественная убыль ресурсов (`Resource::decay`).
+    Decay,
+
+    /// Поведение фауны указанного вида (`FaunaBehaviour`).
+    Fauna(FaunaId),
+
+    /// Идущее глобальное событие.
+    Event(EventId),
 }
 
+/// Вклад одного эффекта в изменение ресурсов Игрока за временной интервал.
+#[derive(Debug, Clone)]
+pub struct EffectContribution {
+    pub source: EffectSource,
+
+    /// `GameState::resource_change` до применения эффекта.
+    pub before: ResourceMap,
+
+    /// `GameState::resource_change` после применения эффекта.
+    pub after: ResourceMap,
+}
+
+/// Выводит сообщение из каталога (см. `tr!`), если `state.verbose == true`,
+/// и записывает его в журнал `state.events`, если он ведётся.
+/// Пригодится модам, правила которых рассказывают о происходящем.
+#[macro_export]
+macro_rules! narrate {
+    ($state:expr, $($arg:tt)*) => {
+        if $state.verbose || $state.events.is_some() {
+            let message = $crate::i18n::tr!($($arg)*);
+            if $state.verbose {
+                println!("{message}");
+            }
+            if let Some(events) = &mut $state.events {
+                events.push(message);
+            }
+        }
+    };
+}
+
+pub use narrate;
+
 /// Дейтствие, предпринимаемое Игроком на каждом временном интервале.
+#[derive(Clone, Copy)]
 pub struct Action {
     /// Новая локация, куда перемещается Игрок в начале следующего
     /// временного интервала, либо `None`, если он остаётся в той же
     /// локации где и был.
     pub destination: Option<&'static Location>,
 
-    /// Занятие, которому предается игрок после смены локации либо `None`,
-    /// если он не делает ничего.
-    pub occupation: Option<Occupation>,
+    /// Занятие (из `OCCUPATIONS`), которому предается игрок после смены
+    /// локации либо `None`, если он не делает ничего.
+    pub occupation: Option<OccupationId>,
+}
+
+/// Причина, по которой действие Игрока не может быть выполнено.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ActionError {
+    /// Из текущей локации нет маршрута в выбранную.
+    NoRoute {
+        from: LocationId,
+        to: LocationId,
+    },
+
+    /// Дорога стоит больше, чем у Игрока есть (или ровно столько, и
+    /// Игрок погиб бы, не сходя с места).
+    CannotAffordTravel {
+        resource: ResourceId,
+        required: f64,
+        available: f64,
+    },
+
+    /// В локации не осталось фауны, без которой занятие бессмысленно.
+    NoFauna {
+        occupation: OccupationId,
+        fauna: FaunaId,
+        location: LocationId,
+    },
+
+    /// Не выполнено условие, без которого занятие недоступно.
+    /// `requirement` - описание условия на языке по умолчанию
+    /// (`Prerequisite::description`).
+    PrerequisiteNotMet {
+        occupation: OccupationId,
+        requirement: &'static str,
+    },
+
+    /// Такого занятия нет в содержимом игры.
+    UnknownOccupation { occupation: OccupationId },
+
+    /// Такой локации нет в содержимом игры.
+    UnknownLocation { location: LocationId },
+
+    /// Мёртвые не совершают действий.
+    PlayerDead,
+}
+
+/// Что пошло не так в симуляции.
+#[derive(Debug, Clone)]
+pub enum SimErrorKind {
+    /// Стратегия выбрала недопустимое действие.
+    IllegalAction(ActionError),
+
+    /// Стратегия запаниковала, выбирая действие.
+    StrategyPanicked {
+        strategy: &'static str,
+        message: String,
+    },
+
+    /// Интервал начат, когда изменения ресурсов за предыдущий ещё не
+    /// применены - предыдущий интервал завершился ошибкой.
+    UnfinishedInterval,
+
+    /// Эффект сделал изменение ресурса бесконечным или неопределённым.
+    NonFiniteChange {
+        source: EffectSource,
+        resource: ResourceId,
+    },
+
+    /// Эффект изменил ресурс не в ту сторону, которую допускают его правила
+    /// (например, занятие, которое должно расходовать ресурс, его принесло).
+    WrongDirection {
+        source: EffectSource,
+        resource: ResourceId,
+        change: f64,
+    },
+
+    /// Параметр правил вне допустимых значений (см. `Param::accepts`).
+    InvalidParam { param: Param, value: f64 },
+
+    /// Параметр правил, объявленный содержимым, вне допустимых значений
+    /// (см. `ParamDef::accepts`).
+    InvalidContentParam { param: ParamId, value: f64 },
+
+    /// Содержимое игры некорректно.
+    Content(ContentErrors),
+
+    /// В содержимом игры нет объекта с таким идентификатором.
+    UnknownId { kind: ContentKind, id: String },
+
+    /// Правила рождения жителей (`Demography`, `Reproduction`) вне
+    /// допустимых значений.
+    InvalidDemography { field: &'static str, value: f64 },
+
+    /// Параметры генетического алгоритма (`GeneticConfig`) вне допустимых
+    /// значений.
+    InvalidGenetic { field: &'static str, value: f64 },
+
+    /// Динамика фауны по умолчанию (`WorldState::default_dynamics`) вне
+    /// допустимых значений (см. `FaunaDynamics::invalid_fields`).
+    InvalidDynamics { field: &'static str, value: f64 },
+
+    /// После временного интервала нарушены инварианты состояния игры
+    /// (только при `GameState::check_invariants`).
+    InvariantViolated(Box<InvariantReport>),
+}
+
+/// Где произошла ошибка симуляции.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimContext {
+    /// Номер временного интервала (`GameState::time_passed`).
+    pub step: usize,
+
+    /// Локация Игрока.
+    pub location: LocationId,
+
+    /// Нация Игрока.
+    pub nation: NationId,
+}
+
+/// Ошибка симуляции вместе с тем, где она произошла, если ошибка
+/// относится к конкретной игре.
+#[derive(Debug, Clone)]
+pub struct SimError {
+    pub kind: SimErrorKind,
+    pub context: Option<SimContext>,
+}
+
+/// Переход между биомами, занимающий несколько временных интервалов.
+#[derive(Clone, Copy)]
+pub struct Transit {
+    /// Действие, которое Игрок совершит по прибытии.
+    pub action: Action,
+
+    /// Локация, куда направляется Игрок.
+    pub destination: &'static Location,
+
+    /// Сколько ещё временных интервалов Игрок проведёт в пути.
+    pub remaining: usize,
 }
 
 /// Стратегия поведения игрока.
@@ -156,6 +367,12 @@ pub trait Strategy {
 
     /// Функция, выбирающаяя действие игрока на очередном шаге.
     fn take_action(&self, state: &mut GameState) -> Action;
+
+    /// Торговля с другими жителями мира перед выбором действия.
+    /// По умолчанию Игрок не торгует.
+    fn trade(&self, _state: &GameState, _market: &MarketView) -> Vec<TradeAction> {
+        Vec::new()
+    }
 }
 
 impl Player {
@@ -163,34 +380,285 @@ impl Player {
         Self {
             name,
             nationality,
-            resources: Resource::iter()
-                .map(|res| (res, STARTING_RESOURCE_AMOUNT))
-                .collect(),
+            resources: starting_resources(),
         }
     }
 
+    /// Сколько ресурса `resource` Игрок может потерять, прежде чем погибнет.
+    pub fn margin(&self, resource: ResourceId) -> f64 {
+        self.resources[resource] - resource.try_get().map_or(0.0, |res| res.death_threshold)
+    }
+
     /// Игрок погибает, если количество любого его ресурса
-    /// становится равным или меньшим нуля.
+    /// становится равным или меньшим порога гибели от этого ресурса.
     pub fn is_dead(&self) -> bool {
-        self.resources.values().any(|&x| x <= 0.0)
+        RESOURCES.iter().any(|res| self.margin(res.id) <= 0.0)
     }
 
     pub fn is_alive(&self) -> bool {
         !self.is_dead()
     }
+
+    /// Ресурс, из-за которого погиб Игрок (если таких несколько - тот,
+    /// которого сильнее всего не хватает), либо `None`, если Игрок жив.
+    pub fn death_cause(&self) -> Option<ResourceId> {
+        RESOURCES
+            .iter()
+            .map(|res| res.id)
+            .filter(|&res| self.margin(res) <= 0.0)
+            .min_by(|&a, &b| self.margin(a).total_cmp(&self.margin(b)))
+    }
+
+    /// Запас самого дефицитного ресурса до порога гибели.
+    pub fn min_resource(&self) -> f64 {
+        RESOURCES
+            .iter()
+            .map(|res| self.margin(res.id))
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+impl EffectContribution {
+    /// Изменение каждого ресурса, внесённое эффектом.
+    pub fn delta(&self) -> ResourceMap {
+        RESOURCES
+            .iter()
+            .map(|res| (res.id, self.after[res.id] - self.before[res.id]))
+            .collect()
+    }
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActionError::NoRoute { from, to } => write!(
+                f,
+                "{}",
+                tr!("error.no_route", from = from.name(), to = to.name())
+            ),
+            ActionError::CannotAffordTravel {
+                resource,
+                required,
+                available,
+            } => write!(
+                f,
+                "{}",
+                tr!(
+                    "error.cannot_afford_travel",
+                    resource = resource.name(),
+                    required = required,
+                    available = available
+                )
+            ),
+            ActionError::NoFauna {
+                occupation,
+                fauna,
+                location,
+            } => write!(
+                f,
+                "{}",
+                tr!(
+                    "error.no_fauna",
+                    occupation = occupation.name(),
+                    fauna = fauna.name(),
+                    location = location.name()
+                )
+            ),
+            ActionError::PrerequisiteNotMet {
+                occupation,
+                requirement,
+            } => write!(
+                f,
+                "{}",
+                tr!(
+                    "error.prerequisite_not_met",
+                    occupation = occupation.name(),
+                    requirement = occupation.requirement(requirement)
+                )
+            ),
+            ActionError::UnknownOccupation { occupation } => write!(
+                f,
+                "{}",
+                tr!("error.unknown_occupation", occupation = occupation)
+            ),
+            ActionError::UnknownLocation { location } => write!(
+                f,
+                "{}",
+                tr!("error.unknown_location", location = location)
+            ),
+            ActionError::PlayerDead => write!(f, "{}", text("error.player_dead")),
+        }
+    }
+}
+
+impl std::error::Error for ActionError {}
+
+impl SimError {
+    /// Ошибка, не относящаяся к конкретной игре.
+    pub fn new(kind: SimErrorKind) -> Self {
+        Self {
+            kind,
+            context: None,
+        }
+    }
+
+    /// Недопустимое действие, если ошибка вызвана им.
+    pub fn action_error(&self) -> Option<ActionError> {
+        match self.kind {
+            SimErrorKind::IllegalAction(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ContentErrors> for SimError {
+    fn from(errors: ContentErrors) -> Self {
+        Self::new(SimErrorKind::Content(errors))
+    }
+}
+
+impl fmt::Display for SimErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimErrorKind::IllegalAction(error) => write!(f, "{error}"),
+            SimErrorKind::StrategyPanicked { strategy, message } => write!(
+                f,
+                "{}",
+                tr!(
+                    "sim_error.strategy_panicked",
+                    strategy = strategy,
+                    message = message
+                )
+            ),
+            SimErrorKind::UnfinishedInterval => {
+                write!(f, "{}", text("sim_error.unfinished_interval"))
+            }
+            SimErrorKind::NonFiniteChange { source, resource } => write!(
+                f,
+                "{}",
+                tr!(
+                    "sim_error.non_finite_change",
+                    source = source,
+                    resource = resource.name()
+                )
+            ),
+            SimErrorKind::WrongDirection {
+                source,
+                resource,
+                change,
+            } => write!(
+                f,
+                "{}",
+                tr!(
+                    "sim_error.wrong_direction",
+                    source = source,
+                    resource = resource.name(),
+                    change = change
+                )
+            ),
+            SimErrorKind::InvalidParam { param, value } => write!(
+                f,
+                "{}",
+                tr!("sim_error.invalid_param", param = param, value = value)
+            ),
+            SimErrorKind::InvalidContentParam { param, value } => write!(
+                f,
+                "{}",
+                tr!("sim_error.invalid_param", param = param, value = value)
+            ),
+            SimErrorKind::Content(errors) => {
+                write!(f, "{}\n{errors}", text("sim_error.content"))
+            }
+            SimErrorKind::UnknownId { kind, id } => {
+                write!(f, "{}", tr!("sim_error.unknown_id", kind = kind, id = id))
+            }
+            SimErrorKind::InvalidDemography { field, value } => write!(
+                f,
+                "{}",
+                tr!("sim_error.invalid_demography", field = field, value = value)
+            ),
+            SimErrorKind::InvalidGenetic { field, value } => write!(
+                f,
+                "{}",
+                tr!("sim_error.invalid_genetic", field = field, value = value)
+            ),
+            SimErrorKind::InvalidDynamics { field, value } => write!(
+                f,
+                "{}",
+                tr!("sim_error.invalid_dynamics", field = field, value = value)
+            ),
+            SimErrorKind::InvariantViolated(report) => write!(f, "{report}"),
+        }
+    }
+}
+
+impl fmt::Display for SimError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(context) = self.context {
+            write!(
+                f,
+                " ({})",
+                tr!(
+                    "sim_error.context",
+                    step = context.step + 1,
+                    location = context.location.name(),
+                    nation = context.nation.name_singular()
+                )
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SimError {}
+
+impl fmt::Display for EffectSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EffectSource::Occupation => write!(f, "{}", text("source.occupation")),
+            EffectSource::Location(id) => {
+                write!(f, "{}", tr!("source.location", location = id.name()))
+            }
+            EffectSource::Nation(id) => {
+                write!(f, "{}", tr!("source.nation", nation = id.name_plural()))
+            }
+            EffectSource::Travel => write!(f, "{}", text("source.travel")),
+            EffectSource::Decay => write!(f, "{}", text("source.decay")),
+            EffectSource::Fauna(fauna) => {
+                write!(f, "{}", tr!("source.fauna", fauna = fauna.name()))
+            }
+            EffectSource::Event(event) => {
+                write!(f, "{}", tr!("source.event", event = event.name()))
+            }
+        }
+    }
+}
+
+impl fmt::Display for EffectContribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let delta = self.delta();
+        let changes: Vec<String> = RESOURCES
+            .iter()
+            .filter(|res| delta[res.id] != 0.0)
+            .map(|res| format!("{} {:+.2}", res.name(), delta[res.id]))
+            .collect();
+        write!(f, "{}: {}", self.source, changes.join(", "))
+    }
 }
 
 impl fmt::Display for Player {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use Resource::*;
+        let resources: Vec<String> = RESOURCES
+            .iter()
+            .map(|res| format!("{}: {:.02}", res.name(), self.resources[res.id]))
+            .collect();
         write!(
             f,
-            "{} [{}] | {Health}: {:.02}, {Money}: {:.02}, {Satisfaction}: {:.02}",
+            "{} [{}] | {}",
             self.name,
-            self.nationality.name_singular,
-            self.resources[Health],
-            self.resources[Money],
-            self.resources[Satisfaction],
+            self.nationality.name_singular(),
+            resources.join(", ")
         )
     }
 }
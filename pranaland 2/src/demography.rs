@@ -0,0 +1,227 @@
+use crate::{
+    definitions::{SimError, SimErrorKind, Strategy},
+    i18n::tr,
+    multiplayer::World,
+    ids::RaceId,
+    nations::{NATIONS, RACES},
+    resources::RESOURCES,
+};
+use rand::Rng;
+use std::{fmt::Write as _, fs, io, path::Path};
+
+/// Правила рождения новых жителей мира.
+pub struct Demography {
+    /// Вероятность того, что за временной интервал в случайной локации
+    /// родится житель случайной нации.
+    birth_chance: f64,
+
+    /// Размножение жителей, либо `None`, если жители не размножаются.
+    reproduction: Option<Reproduction>,
+
+    /// Больше этого количества живых жителей в мире не рождается.
+    max_population: usize,
+
+    /// Стратегия для каждого новорождённого.
+    strategy: Box<dyn Fn() -> Box<dyn Strategy>>,
+}
+
+/// Правила размножения жителей.
+#[derive(Debug, Clone, Copy)]
+pub struct Reproduction {
+    /// Житель может завести ребёнка, только если каждого его ресурса
+    /// не меньше этого порога.
+    threshold: f64,
+
+    /// Вероятность завести ребёнка за временной интервал.
+    chance: f64,
+}
+
+impl Demography {
+    /// Правила рождения. `birth_chance` - вероятность, иначе ошибка
+    /// `SimErrorKind::InvalidDemography`.
+    pub fn new(
+        birth_chance: f64,
+        reproduction: Option<Reproduction>,
+        max_population: usize,
+        strategy: Box<dyn Fn() -> Box<dyn Strategy>>,
+    ) -> Result<Self, SimError> {
+        check_probability("birth_chance", birth_chance)?;
+        Ok(Self {
+            birth_chance,
+            reproduction,
+            max_population,
+            strategy,
+        })
+    }
+
+    pub fn birth_chance(&self) -> f64 {
+        self.birth_chance
+    }
+
+    pub fn reproduction(&self) -> Option<Reproduction> {
+        self.reproduction
+    }
+
+    pub fn max_population(&self) -> usize {
+        self.max_population
+    }
+
+    /// Стратегия для очередного новорождённого.
+    pub fn new_strategy(&self) -> Box<dyn Strategy> {
+        (self.strategy)()
+    }
+}
+
+impl Reproduction {
+    /// Правила размножения. `chance` - вероятность, а порог `threshold`
+    /// больше `Resource::starting_amount + Resource::death_threshold`
+    /// каждого ресурса, чтобы родитель не погиб, отдав ребёнку его ресурсы.
+    /// Иначе - ошибка `SimErrorKind::InvalidDemography`.
+    pub fn new(threshold: f64, chance: f64) -> Result<Self, SimError> {
+        check_probability("chance", chance)?;
+        let viable = RESOURCES
+            .iter()
+            .all(|res| threshold > res.starting_amount + res.death_threshold);
+        if !threshold.is_finite() || !viable {
+            return Err(SimError::new(SimErrorKind::InvalidDemography {
+                field: "threshold",
+                value: threshold,
+            }));
+        }
+        Ok(Self { threshold, chance })
+    }
+
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    pub fn chance(&self) -> f64 {
+        self.chance
+    }
+}
+
+fn check_probability(field: &'static str, value: f64) -> Result<(), SimError> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(())
+    } else {
+        Err(SimError::new(SimErrorKind::InvalidDemography { field, value }))
+    }
+}
+
+/// Перепись населения на одном временном интервале.
+#[derive(Debug, Clone)]
+pub struct Census {
+    pub time: usize,
+
+    /// Количество живых жителей каждой нации (в порядке `NATIONS`).
+    pub by_nation: Vec<usize>,
+}
+
+impl Census {
+    pub fn total(&self) -> usize {
+        self.by_nation.iter().sum()
+    }
+
+    /// Количество живых жителей расы `race`.
+    pub fn race(&self, race: RaceId) -> usize {
+        NATIONS
+            .iter()
+            .zip(&self.by_nation)
+            .filter(|(nation, _)| nation.race == race)
+            .map(|(_, count)| count)
+            .sum()
+    }
+}
+
+impl World {
+    /// Перепись живых жителей. Ошибка - если нации жителя нет в `NATIONS`.
+    pub fn take_census(&self) -> Result<Census, SimError> {
+        let mut by_nation = vec![0; NATIONS.len()];
+        for inhabitant in self.alive() {
+            by_nation[inhabitant.state.player.nationality.index()?] += 1;
+        }
+        Ok(Census {
+            time: self.time_passed,
+            by_nation,
+        })
+    }
+
+    /// Рождение новых жителей по правилам `demography`.
+    pub(crate) fn give_births(&mut self) -> Result<(), SimError> {
+        let Some(demography) = self.demography.take() else {
+            return Ok(());
+        };
+        let result = self.births(&demography);
+        self.demography = Some(demography);
+        result
+    }
+
+    /// Ребёнок получает нацию родителя и рождается в его локации, а родитель
+    /// отдаёт ему ресурсы, с которыми обычно рождаются жители.
+    fn births(&mut self, demography: &Demography) -> Result<(), SimError> {
+        let mut population = self.alive().count();
+
+        if let Some(reproduction) = demography.reproduction {
+            for parent in 0..self.inhabitants.len() {
+                if population >= demography.max_population {
+                    break;
+                }
+
+                let player = &self.inhabitants[parent].state.player;
+                let ready = player.is_alive()
+                    && RESOURCES
+                        .iter()
+                        .all(|res| player.resources[res.id] >= reproduction.threshold);
+                if !ready || !self.rng.gen_bool(reproduction.chance) {
+                    continue;
+                }
+
+                let nation = player.nationality;
+                let location = self.inhabitants[parent].state.location;
+                for res in RESOURCES.iter() {
+                    self.inhabitants[parent].state.player.resources[res.id] -= res.starting_amount;
+                }
+
+                let name = tr!("world.inhabitant", n = self.inhabitants.len() + 1);
+                let fauna = self.world.fauna_in(location)?;
+                let child = self.add_inhabitant(name, Some(nation), (demography.strategy)())?;
+                child.location = location;
+                child.location_history = vec![location];
+                child.effective_fauna = fauna;
+                population += 1;
+            }
+        }
+
+        if population < demography.max_population && self.rng.gen_bool(demography.birth_chance) {
+            let name = tr!("world.inhabitant", n = self.inhabitants.len() + 1);
+            self.add_inhabitant(name, None, (demography.strategy)())?;
+        }
+        Ok(())
+    }
+}
+
+/// Сохраняет результаты переписей в CSV: по строке на интервал, по столбцу
+/// на каждую расу и нацию (в заголовках - их идентификаторы).
+pub fn write_census_csv(census: &[Census], path: &Path) -> io::Result<()> {
+    let mut csv = String::from("time,total");
+    for race in RACES.iter() {
+        write!(csv, ",{}", race.id).unwrap();
+    }
+    for nation in NATIONS.iter() {
+        write!(csv, ",{}", nation.id).unwrap();
+    }
+    csv.push('\n');
+
+    for entry in census {
+        write!(csv, "{},{}", entry.time, entry.total()).unwrap();
+        for race in RACES.iter() {
+            write!(csv, ",{}", entry.race(race.id)).unwrap();
+        }
+        for count in &entry.by_nation {
+            write!(csv, ",{count}").unwrap();
+        }
+        csv.push('\n');
+    }
+
+    fs::write(path, csv)
+}
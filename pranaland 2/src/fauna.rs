@@ -0,0 +1,162 @@
+use crate::{
+    definitions::{narrate, FaunaMap, GameState, SimError, SimErrorKind},
+    i18n::text_or,
+    ids::{FaunaId, OccupationId, ResourceId},
+    occupations::OCCUPATIONS,
+    params::Param,
+    registry::{content, ContentKind},
+};
+use lazy_static::lazy_static;
+
+/// Особое поведение фауны, проявляющееся на каждом временном интервале,
+/// пока Игрок находится рядом с ней.
+#[derive(Debug, Clone, Copy)]
+pub enum FaunaBehaviour {
+    /// Каждый представитель фауны с вероятностью `chance` наносит Игроку
+    /// урон `damage` ресурсу `resource`.
+    Aggressive {
+        resource: ResourceId,
+        chance: Param,
+        damage: Param,
+    },
+}
+
+/// Слесандры - питают зумбаление.
+pub const SLESANDRA: FaunaId = FaunaId("slesandra");
+
+/// Сисяндры - питают гульбонство.
+pub const SISYANDRA: FaunaId = FaunaId("sisyandra");
+
+/// Чучундры - питают шлямсание.
+pub const CHUCHUNDRA: FaunaId = FaunaId("chuchundra");
+
+/// Вид фауны и его свойства.
+pub struct Fauna {
+    pub id: FaunaId,
+
+    /// Название вида на языке по умолчанию ("Слесандры").
+    /// Перевод - в каталоге сообщений по ключу `fauna.<id>.name`.
+    pub name: &'static str,
+
+    /// Название одного представителя ("Слесандра").
+    /// Перевод - в каталоге сообщений по ключу `fauna.<id>.one`.
+    pub name_singular: &'static str,
+
+    /// Поведение, присущее виду в любой локации. Поведение, проявляющееся
+    /// только в некоторых локациях, описывается в `Location::fauna_behaviours`.
+    pub behaviours: Vec<FaunaBehaviour>,
+}
+
+lazy_static! {
+    /// Все виды фауны из установленного содержимого игры (`content()`).
+    /// Чтобы добавить вид, достаточно описать его в наборе содержимого,
+    /// поселить в локации (`Location::fauna`) и указать в занятии,
+    /// которое он питает (`Occupation::fauna`).
+    pub static ref FAUNA: &'static [Fauna] =
+        content().map(|content| content.fauna.as_slice()).unwrap_or_default();
+}
+
+/// Фауна базового набора содержимого.
+pub fn base_fauna() -> Vec<Fauna> {
+    vec![
+        Fauna {
+            id: SLESANDRA,
+            name: "Слесандры",
+            name_singular: "Слесандра",
+            behaviours: vec![],
+        },
+        Fauna {
+            id: SISYANDRA,
+            name: "Сисяндры",
+            name_singular: "Сисяндра",
+            behaviours: vec![],
+        },
+        Fauna {
+            id: CHUCHUNDRA,
+            name: "Чучундры",
+            name_singular: "Чучундра",
+            behaviours: vec![],
+        },
+    ]
+}
+
+impl Fauna {
+    /// Название вида на текущем языке.
+    pub fn name(&self) -> &'static str {
+        text_or(&format!("fauna.{}.name", self.id), self.name)
+    }
+
+    /// Название одного представителя на текущем языке.
+    pub fn name_singular(&self) -> &'static str {
+        text_or(&format!("fauna.{}.one", self.id), self.name_singular)
+    }
+
+    /// Занятия, доход от которых зависит от этого вида фауны.
+    pub fn occupations(&self) -> impl Iterator<Item = OccupationId> {
+        let id = self.id;
+        OCCUPATIONS
+            .iter()
+            .filter(move |occupation| occupation.fauna.iter().any(|&(fauna, _)| fauna == id))
+            .map(|occupation| occupation.id)
+    }
+}
+
+impl FaunaId {
+    pub fn try_get(self) -> Result<&'static Fauna, SimError> {
+        FAUNA.iter().find(|fauna| fauna.id == self).ok_or_else(|| {
+            SimError::new(SimErrorKind::UnknownId {
+                kind: ContentKind::Fauna,
+                id: self.to_string(),
+            })
+        })
+    }
+
+    /// Название вида на текущем языке, а если такого вида нет
+    /// в содержимом игры - его идентификатор.
+    pub fn name(self) -> &'static str {
+        self.try_get().map_or(self.0, Fauna::name)
+    }
+
+    /// Название одного представителя на текущем языке.
+    pub fn name_singular(self) -> &'static str {
+        self.try_get().map_or(self.0, Fauna::name_singular)
+    }
+}
+
+impl FaunaBehaviour {
+    /// Применяет поведение всех работающих представителей фауны `fauna`
+    /// в локации Игрока.
+    pub fn apply(&self, fauna: FaunaId, state: &mut GameState) {
+        match *self {
+            FaunaBehaviour::Aggressive {
+                resource,
+                chance,
+                damage,
+            } => {
+                let damage = state.params[damage];
+                for i in 0..state.effective_fauna[fauna] {
+                    if state.chance(state.params[chance]) {
+                        narrate!(
+                            state,
+                            "fauna.aggressive",
+                            fauna = fauna.name_singular(),
+                            n = i + 1,
+                            resource = resource.name(),
+                            damage = damage
+                        );
+                        state.resource_change[resource] -= damage;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `FaunaMap` с указанной численностью фауны (остальных видов нет).
+pub fn counts(pairs: &[(FaunaId, usize)]) -> FaunaMap {
+    let mut map = FaunaMap::default();
+    for &(fauna, count) in pairs {
+        map[fauna] += count;
+    }
+    map
+}
@@ -0,0 +1,250 @@
+use crate::{
+    batch::{run_batch, BatchConfig},
+    definitions::{SimError, SimErrorKind},
+    locations::LOCATIONS,
+    nations::Nation,
+    resources::RESOURCES,
+    risk::Objective,
+    strategies::{RuleGenome, RuleStrategy},
+};
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Параметры генетического алгоритма. Параметры отбора и мутаций
+/// проверяются при создании (`GeneticConfig::new`).
+#[derive(Debug, Clone, Copy)]
+pub struct GeneticConfig {
+    /// Размер популяции.
+    population: usize,
+
+    /// Количество поколений.
+    pub generations: usize,
+
+    /// Сколько лучших особей переходит в следующее поколение без изменений.
+    elite: usize,
+
+    /// Количество участников турнира при выборе родителя.
+    tournament_size: usize,
+
+    /// Вероятность мутации каждого гена.
+    mutation_rate: f64,
+
+    /// Максимальный сдвиг порога при мутации.
+    mutation_scale: f64,
+
+    /// Пороги генома лежат в диапазоне `0.0..=max_threshold`.
+    max_threshold: f64,
+
+    /// Зерно генератора псевдослучайных чисел самого алгоритма.
+    pub seed: u64,
+
+    /// Параметры пакетного прогона, по которому считается приспособленность.
+    pub batch: BatchConfig,
+
+    /// Критерий, по которому пакетный прогон превращается в приспособленность.
+    pub objective: Objective,
+}
+
+/// Лучшая особь, найденная генетическим алгоритмом.
+#[derive(Clone)]
+pub struct Evolved {
+    pub genome: RuleGenome,
+    pub fitness: f64,
+}
+
+impl Default for GeneticConfig {
+    fn default() -> Self {
+        Self {
+            population: 30,
+            generations: 20,
+            elite: 2,
+            tournament_size: 3,
+            mutation_rate: 0.2,
+            mutation_scale: 3.0,
+            max_threshold: 20.0,
+            seed: 42,
+            batch: BatchConfig {
+                games: 30,
+                ..Default::default()
+            },
+            objective: Objective::MeanSurvival,
+        }
+    }
+}
+
+impl GeneticConfig {
+    /// Параметры отбора и мутаций; остальные - по умолчанию. Популяция
+    /// больше элиты, в турнире есть участники, `mutation_rate` - вероятность,
+    /// а `mutation_scale` и `max_threshold` неотрицательны. Иначе - ошибка
+    /// `SimErrorKind::InvalidGenetic`.
+    pub fn new(
+        population: usize,
+        elite: usize,
+        tournament_size: usize,
+        mutation_rate: f64,
+        mutation_scale: f64,
+        max_threshold: f64,
+    ) -> Result<Self, SimError> {
+        let invalid =
+            |field, value| Err(SimError::new(SimErrorKind::InvalidGenetic { field, value }));
+        if population <= elite {
+            return invalid("elite", elite as f64);
+        }
+        if tournament_size == 0 {
+            return invalid("tournament_size", 0.0);
+        }
+        if !(0.0..=1.0).contains(&mutation_rate) {
+            return invalid("mutation_rate", mutation_rate);
+        }
+        if !(mutation_scale.is_finite() && mutation_scale >= 0.0) {
+            return invalid("mutation_scale", mutation_scale);
+        }
+        if !(max_threshold.is_finite() && max_threshold >= 0.0) {
+            return invalid("max_threshold", max_threshold);
+        }
+        Ok(Self {
+            population,
+            elite,
+            tournament_size,
+            mutation_rate,
+            mutation_scale,
+            max_threshold,
+            ..Self::default()
+        })
+    }
+
+    pub fn population(&self) -> usize {
+        self.population
+    }
+
+    pub fn elite(&self) -> usize {
+        self.elite
+    }
+
+    pub fn tournament_size(&self) -> usize {
+        self.tournament_size
+    }
+
+    pub fn mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    pub fn mutation_scale(&self) -> f64 {
+        self.mutation_scale
+    }
+
+    pub fn max_threshold(&self) -> f64 {
+        self.max_threshold
+    }
+}
+
+/// Приспособленность генома для конкретной нации - оценка пакетного
+/// прогона по критерию `config.objective`.
+pub fn nation_fitness(genome: &RuleGenome, nation: &'static Nation, config: &GeneticConfig) -> f64 {
+    let strategy = RuleStrategy {
+        genome: genome.clone(),
+    };
+    config
+        .objective
+        .score(&run_batch(&strategy, Some(nation), &config.batch))
+}
+
+/// Подбирает геном стратегии на правилах, максимизирующий `fitness`.
+pub fn evolve(config: &GeneticConfig, fitness: &dyn Fn(&RuleGenome) -> f64) -> Evolved {
+    let mut rng = ChaCha8Rng::seed_from_u64(config.seed);
+
+    let mut population: Vec<Evolved> = (0..config.population)
+        .map(|_| {
+            let genome = random_genome(&mut rng, config);
+            let fitness = fitness(&genome);
+            Evolved { genome, fitness }
+        })
+        .collect();
+
+    for _ in 0..config.generations {
+        population.sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
+
+        let mut next: Vec<Evolved> = population[..config.elite].to_vec();
+
+        while next.len() < config.population {
+            let (Some(mother), Some(father)) = (
+                tournament(&population, &mut rng, config),
+                tournament(&population, &mut rng, config),
+            ) else {
+                break;
+            };
+            let mut genome = crossover(mother, father, &mut rng);
+            mutate(&mut genome, &mut rng, config);
+            let fitness = fitness(&genome);
+            next.push(Evolved { genome, fitness });
+        }
+
+        population = next;
+    }
+
+    // Популяция не пуста, пока она больше элиты (см. `GeneticConfig::new`).
+    population
+        .into_iter()
+        .max_by(|a, b| a.fitness.total_cmp(&b.fitness))
+        .unwrap_or_else(|| {
+            let genome = random_genome(&mut rng, config);
+            let fitness = fitness(&genome);
+            Evolved { genome, fitness }
+        })
+}
+
+fn random_genome(rng: &mut ChaCha8Rng, config: &GeneticConfig) -> RuleGenome {
+    RuleGenome {
+        thresholds: RESOURCES
+            .iter()
+            .map(|res| (res.id, rng.gen_range(0.0..=config.max_threshold)))
+            .collect(),
+        locations: RESOURCES
+            .iter()
+            .filter_map(|res| Some((res.id, LOCATIONS.choose(rng)?)))
+            .collect(),
+    }
+}
+
+/// Лучшая из `tournament_size` случайных особей, либо `None`, если выбирать
+/// не из кого.
+fn tournament<'a>(
+    population: &'a [Evolved],
+    rng: &mut ChaCha8Rng,
+    config: &GeneticConfig,
+) -> Option<&'a RuleGenome> {
+    population
+        .choose_multiple(rng, config.tournament_size)
+        .max_by(|a, b| a.fitness.total_cmp(&b.fitness))
+        .map(|evolved| &evolved.genome)
+}
+
+fn crossover(mother: &RuleGenome, father: &RuleGenome, rng: &mut ChaCha8Rng) -> RuleGenome {
+    let mut child = mother.clone();
+    for res in RESOURCES.iter().map(|res| res.id) {
+        if rng.gen_bool(0.5) {
+            child.thresholds[res] = father.thresholds[res];
+        }
+        if rng.gen_bool(0.5) {
+            if let Some(&location) = father.locations.get(&res) {
+                child.locations.insert(res, location);
+            }
+        }
+    }
+    child
+}
+
+fn mutate(genome: &mut RuleGenome, rng: &mut ChaCha8Rng, config: &GeneticConfig) {
+    for res in RESOURCES.iter().map(|res| res.id) {
+        if rng.gen_bool(config.mutation_rate) {
+            let shift = rng.gen_range(-config.mutation_scale..=config.mutation_scale);
+            genome.thresholds[res] =
+                (genome.thresholds[res] + shift).clamp(0.0, config.max_threshold);
+        }
+        if rng.gen_bool(config.mutation_rate) {
+            if let Some(location) = LOCATIONS.choose(rng) {
+                genome.locations.insert(res, location);
+            }
+        }
+    }
+}
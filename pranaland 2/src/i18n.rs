@@ -0,0 +1,247 @@
+//! Локализация текстов, которые видит Игрок.
+//!
+//! Тексты хранятся в каталогах сообщений `locales/<язык>.txt` - по строке
+//! `ключ = шаблон` на сообщение (пробелы в начале шаблона после первого
+//! сохраняются). Пустые строки и строки, начинающиеся с `#`, пропускаются,
+//! `\n` в шаблоне означает перевод строки.
+//!
+//! В шаблоне можно подставлять аргументы:
+//! - `{name}` - значение аргумента `name` как есть;
+//! - `{name:.2}` - число с указанным количеством знаков после запятой;
+//! - `{name:%}` - доля в процентах (`0.1` - "10");
+//! - `{name:+%}` - на сколько процентов множитель больше единицы (`1.2` - "20");
+//! - `{name:<16}`, `{name:>7.2}`, `{name:^10}` - значение, выровненное
+//!   по левому краю, правому краю или центру поля указанной ширины (как
+//!   в `format!`, без выравнивания числа прижимаются вправо, а текст - влево);
+//! - `{name|форма|формы|форм}` - форма слова, согласованная с числом `name`
+//!   по правилам языка (в русском - три формы, в английском - две).
+//!
+//! Род в шаблонах не угадывается: сообщения об Игроке пишутся так, чтобы
+//! не зависеть от его пола ("Жора погибает", а не "Жора мёртв").
+//!
+//! Названия содержимого игры (локаций, наций и т.д.) по умолчанию берутся
+//! из набора содержимого, а каталог может их перевести по ключу вида
+//! `location.<id>.name`.
+//!
+//! Наборы содержимого приносят свои каталоги (`ContentPack::messages`) в том
+//! же формате: сообщения установленного набора имеют приоритет над
+//! каталогами игры, так что мод может и добавить тексты своего содержимого,
+//! и заменить существующие.
+
+use crate::registry;
+use enum_map::EnumMap;
+use lazy_static::lazy_static;
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Язык текстов игры.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_map::Enum, strum::EnumString)]
+pub enum Locale {
+    #[strum(serialize = "ru")]
+    Ru,
+
+    #[strum(serialize = "en")]
+    En,
+}
+
+/// Каталог сообщений одного языка: ключ -> шаблон.
+pub type Catalog = HashMap<&'static str, String>;
+
+lazy_static! {
+    static ref CATALOGS: EnumMap<Locale, Catalog> = EnumMap::from_fn(|locale| {
+        let source = match locale {
+            Locale::Ru => include_str!("../locales/ru.txt"),
+            Locale::En => include_str!("../locales/en.txt"),
+        };
+        parse_catalog(source).unwrap_or_else(|line| panic!("Malformed catalog line: {line}"))
+    });
+}
+
+/// Индекс текущего языка в `Locale`.
+static LOCALE: AtomicUsize = AtomicUsize::new(0);
+
+/// Разбирает каталог сообщений (см. описание модуля). Если строка
+/// не имеет вида `ключ = шаблон`, возвращает её.
+pub(crate) fn parse_catalog(source: &'static str) -> Result<Catalog, &'static str> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (key, template) = line.split_once('=').ok_or(line)?;
+            let template = template.strip_prefix(' ').unwrap_or(template);
+            Ok((key.trim(), template.trim_end().replace("\\n", "\n")))
+        })
+        .collect()
+}
+
+/// Текущий язык текстов.
+pub fn locale() -> Locale {
+    <Locale as enum_map::Enum>::from_usize(LOCALE.load(Ordering::Relaxed))
+}
+
+/// Меняет язык текстов, выводимых после этого вызова.
+pub fn set_locale(locale: Locale) {
+    LOCALE.store(enum_map::Enum::into_usize(locale), Ordering::Relaxed);
+}
+
+/// Шаблон сообщения `key` на текущем языке: из каталогов установленного
+/// содержимого, а если там его нет - из каталогов игры. Если перевода нет,
+/// используется русский шаблон.
+pub fn lookup(key: &str) -> Option<&'static str> {
+    let packs = registry::installed().map(|content| &content.messages);
+    let find = |locale: Locale| {
+        packs
+            .and_then(|messages| messages[locale].get(key))
+            .or_else(|| CATALOGS[locale].get(key))
+    };
+    find(locale())
+        .or_else(|| find(Locale::Ru))
+        .map(String::as_str)
+}
+
+/// Сообщение `key` без аргументов на текущем языке. Если сообщения нет
+/// в каталогах, возвращается сам ключ, чтобы пропуск был заметен.
+pub fn text(key: &'static str) -> &'static str {
+    lookup(key).unwrap_or(key)
+}
+
+/// Перевод текста содержимого игры по ключу `key`. Если перевода нет,
+/// возвращается `default` - текст из набора содержимого.
+pub fn text_or(key: &str, default: &'static str) -> &'static str {
+    lookup(key).unwrap_or(default)
+}
+
+/// Номер формы слова, согласованной с числом `n`, в шаблоне вида
+/// `{n|форма|формы|форм}`.
+fn plural_form(locale: Locale, n: f64) -> usize {
+    match locale {
+        Locale::Ru => {
+            if n.fract() != 0.0 {
+                return 1;
+            }
+            let n = n.abs() as u64;
+            if n % 10 == 1 && n % 100 != 11 {
+                0
+            } else if (2..=4).contains(&(n % 10)) && !(12..=14).contains(&(n % 100)) {
+                1
+            } else {
+                2
+            }
+        }
+        Locale::En => usize::from(n != 1.0),
+    }
+}
+
+/// Подставляет аргументы `args` в шаблон `template` (см. описание модуля).
+pub fn render(template: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            rest = &rest[start..];
+            break;
+        };
+        let placeholder = &rest[start + 1..end];
+        rest = &rest[end + 1..];
+
+        let (name, spec) = match placeholder.find(['|', ':']) {
+            Some(i) => (&placeholder[..i], Some(&placeholder[i..])),
+            None => (placeholder, None),
+        };
+        let Some((_, value)) = args.iter().find(|(arg, _)| *arg == name) else {
+            result.push('{');
+            result.push_str(placeholder);
+            result.push('}');
+            continue;
+        };
+
+        let value = value.to_string();
+        let number = value.parse::<f64>().ok();
+        match (spec, number) {
+            (Some(forms), Some(n)) if forms.starts_with('|') => {
+                let forms: Vec<&str> = forms[1..].split('|').collect();
+                let form = plural_form(locale(), n).min(forms.len() - 1);
+                result.push_str(forms[form]);
+            }
+            (Some(format), _) if format.starts_with(':') => {
+                result.push_str(&format_value(&format[1..], value, number));
+            }
+            _ => result.push_str(&value),
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Значение аргумента `value` (и оно же как число `number`, если это число),
+/// отформатированное по спецификации вида `>7.2` (см. описание модуля).
+fn format_value(format: &str, value: String, number: Option<f64>) -> String {
+    let (align, format) = match format.chars().next() {
+        Some(align @ ('<' | '>' | '^')) => (Some(align), &format[1..]),
+        _ => (None, format),
+    };
+    let digits = format
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(format.len());
+    let width = format[..digits].parse().unwrap_or(0);
+
+    let value = match (&format[digits..], number) {
+        ("%", Some(n)) => percent(n),
+        ("+%", Some(n)) => percent(n - 1.0),
+        (precision, Some(n)) if precision.starts_with('.') => {
+            let precision = precision[1..].parse().unwrap_or(2);
+            format!("{n:.precision$}")
+        }
+        _ => value,
+    };
+
+    match align.unwrap_or(if number.is_some() { '>' } else { '<' }) {
+        '<' => format!("{value:<width$}"),
+        '^' => format!("{value:^width$}"),
+        _ => format!("{value:>width$}"),
+    }
+}
+
+/// Доля `share` в процентах, без лишних нулей после запятой.
+fn percent(share: f64) -> String {
+    let percent = format!("{:.2}", share * 100.0);
+    percent.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// Сообщение `key` на текущем языке с подставленными аргументами.
+/// Обычно вызывается через макрос `tr!`.
+pub fn translate(key: &'static str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    render(text(key), args)
+}
+
+/// Сообщение из каталога с именованными аргументами:
+/// `tr!("play.dead", name = state.player.name, n = state.time_passed)`.
+#[macro_export]
+macro_rules! tr {
+    ($key:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::i18n::translate($key, &[$((stringify!($name), &($value) as &dyn std::fmt::Display)),*])
+    };
+}
+
+pub use tr;
+
+/// Реализует `Display` для перечисления через сообщения `<prefix>.<Вариант>`.
+macro_rules! localized_display {
+    ($($type:ty => $prefix:literal),* $(,)?) => {
+        $(
+            impl std::fmt::Display for $type {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    let key = format!(concat!($prefix, ".{:?}"), self);
+                    f.pad($crate::i18n::lookup(&key).unwrap_or(&key))
+                }
+            }
+        )*
+    };
+}
+
+pub(crate) use localized_display;
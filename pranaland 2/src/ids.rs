@@ -0,0 +1,216 @@
+//! Стабильные идентификаторы содержимого игры.
+//!
+//! Идентификатор - короткий латинский ключ ("balbesburg"), который не
+//! меняется при переименовании или переводе. Сравнивать, хэшировать и
+//! сохранять локации, нации, расы, ресурсы и фауну следует по
+//! идентификаторам, а не по названиям или адресам в памяти.
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+/// Идентификатор локации в `LOCATIONS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LocationId(pub &'static str);
+
+/// Идентификатор нации в `NATIONS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NationId(pub &'static str);
+
+/// Идентификатор расы в `RACES`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RaceId(pub &'static str);
+
+/// Идентификатор биома в `BIOMES`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BiomeId(pub &'static str);
+
+/// Идентификатор занятия в `OCCUPATIONS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct OccupationId(pub &'static str);
+
+/// Идентификатор ресурса в `RESOURCES`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ResourceId(pub &'static str);
+
+/// Идентификатор вида фауны в `FAUNA`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FaunaId(pub &'static str);
+
+/// Идентификатор глобального события в `EVENTS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EventId(pub &'static str);
+
+/// Идентификатор параметра правил, объявленного содержимым (`ParamDef`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ParamId(pub &'static str);
+
+/// Отображение из идентификаторов в значения.
+///
+/// Хранятся только записанные значения, для остальных идентификаторов
+/// возвращается значение по умолчанию. Какие идентификаторы существуют,
+/// знает только содержимое игры, поэтому перебирать, например, все ресурсы
+/// нужно по `RESOURCES`, а не по отображению: `iter` возвращает только
+/// записанные значения.
+#[derive(Debug, Clone)]
+pub struct IdMap<K, V> {
+    entries: Vec<(K, V)>,
+    default: V,
+}
+
+impl fmt::Display for LocationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for NationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for RaceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for BiomeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for OccupationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for ResourceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for FaunaId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for EventId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for ParamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<K: Copy + Eq, V: Clone> IdMap<K, V> {
+    /// Отображение, в котором у всех идентификаторов значение `default`.
+    pub fn filled(default: V) -> Self {
+        Self {
+            entries: Vec::new(),
+            default,
+        }
+    }
+
+    /// Записанные значения в порядке записи.
+    pub fn iter(&self) -> impl Iterator<Item = (K, &V)> {
+        self.entries.iter().map(|(key, value)| (*key, value))
+    }
+
+    /// Записанные значения в порядке записи.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (K, &mut V)> {
+        self.entries.iter_mut().map(|(key, value)| (*key, value))
+    }
+
+    /// Записанные значения без идентификаторов.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.entries.iter().map(|(_, value)| value)
+    }
+}
+
+impl<K, V: Default> Default for IdMap<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            default: V::default(),
+        }
+    }
+}
+
+impl<K: Copy + Eq, V> Index<K> for IdMap<K, V> {
+    type Output = V;
+
+    fn index(&self, key: K) -> &V {
+        self.entries
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map_or(&self.default, |(_, value)| value)
+    }
+}
+
+impl<K: Copy + Eq, V: Clone> IndexMut<K> for IdMap<K, V> {
+    fn index_mut(&mut self, key: K) -> &mut V {
+        let i = match self.entries.iter().position(|(k, _)| *k == key) {
+            Some(i) => i,
+            None => {
+                self.entries.push((key, self.default.clone()));
+                self.entries.len() - 1
+            }
+        };
+        &mut self.entries[i].1
+    }
+}
+
+impl<K: Copy + Eq, V: Clone + Default> FromIterator<(K, V)> for IdMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::default();
+        for (key, value) in iter {
+            map[key] = value;
+        }
+        map
+    }
+}
+
+impl<K, V> IntoIterator for IdMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = std::vec::IntoIter<(K, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a, K: Copy + Eq, V: Clone> IntoIterator for &'a IdMap<K, V> {
+    type Item = (K, &'a V);
+    type IntoIter = std::iter::Map<std::slice::Iter<'a, (K, V)>, fn(&'a (K, V)) -> (K, &'a V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter().map(|(key, value)| (*key, value))
+    }
+}
+
+/// Два отображения равны, если у них одинаковые значения для всех
+/// идентификаторов - и записанных, и нет.
+impl<K: Copy + Eq, V: PartialEq> PartialEq for IdMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.default == other.default
+            && self
+                .entries
+                .iter()
+                .all(|(key, value)| other[*key] == *value)
+            && other
+                .entries
+                .iter()
+                .all(|(key, value)| self[*key] == *value)
+    }
+}
@@ -0,0 +1,184 @@
+use crate::{
+    definitions::{Action, GameState, SimError},
+    nations::NATIONS,
+    resources::RESOURCES,
+    strategies::FixedActionStrategy,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::collections::HashMap;
+
+/// Наблюдаемый исход действия - изменение каждого ресурса Игрока,
+/// округлённое до сотых.
+pub type Observation = Vec<i64>;
+
+/// Представление Игрока о своей нации - вероятность каждой нации
+/// (в порядке `NATIONS`).
+#[derive(Debug, Clone)]
+pub struct Belief {
+    pub probabilities: Vec<f64>,
+}
+
+/// Распределение исходов действия для каждой нации (в порядке `NATIONS`).
+pub struct OutcomeModel {
+    pub likelihoods: Vec<HashMap<Observation, f64>>,
+}
+
+/// Ценность действия как источника информации о своей нации.
+pub struct ActionValue {
+    pub action: Action,
+
+    /// Ожидаемый прирост информации о нации, в битах.
+    pub information_gain: f64,
+
+    /// Ожидаемое уменьшение самого маленького из ресурсов Игрока -
+    /// насколько это действие в среднем приближает его к смерти.
+    pub expected_cost: f64,
+}
+
+impl Belief {
+    /// Игрок ничего не знает о своей нации.
+    pub fn uniform() -> Self {
+        Self {
+            probabilities: vec![1.0 / NATIONS.len() as f64; NATIONS.len()],
+        }
+    }
+
+    /// Энтропия представления в битах.
+    pub fn entropy(&self) -> f64 {
+        self.probabilities
+            .iter()
+            .filter(|&&p| p > 0.0)
+            .map(|&p| -p * p.log2())
+            .sum()
+    }
+
+    /// Обновляет представление по формуле Байеса после того, как действие
+    /// с моделью исходов `model` привело к исходу `observation`.
+    /// Если исход невозможен ни для одной нации, представление не меняется.
+    pub fn update(&mut self, model: &OutcomeModel, observation: &Observation) {
+        let posterior: Vec<f64> = self
+            .probabilities
+            .iter()
+            .zip(&model.likelihoods)
+            .map(|(p, likelihood)| p * likelihood.get(observation).copied().unwrap_or(0.0))
+            .collect();
+
+        let total: f64 = posterior.iter().sum();
+        if total > 0.0 {
+            self.probabilities = posterior.into_iter().map(|p| p / total).collect();
+        }
+    }
+}
+
+/// Исход одного временного интервала: изменение ресурсов Игрока.
+pub fn observe(before: &GameState, after: &GameState) -> Observation {
+    RESOURCES
+        .iter()
+        .map(|res| {
+            let delta = after.player.resources[res.id] - before.player.resources[res.id];
+            (delta * 100.0).round() as i64
+        })
+        .collect()
+}
+
+/// Оценивает распределение исходов допустимого действия `action` в состоянии
+/// `state` для каждой нации, прогоняя по `samples` симуляций одного интервала.
+/// Вместе с моделью возвращает ожидаемое уменьшение минимального ресурса
+/// для каждой нации.
+pub fn outcome_model(
+    state: &GameState,
+    action: Action,
+    samples: usize,
+) -> Result<(OutcomeModel, Vec<f64>), SimError> {
+    let strategy = FixedActionStrategy { action };
+    let min_before = state.player.min_resource();
+
+    let mut likelihoods = Vec::with_capacity(NATIONS.len());
+    let mut costs = Vec::with_capacity(NATIONS.len());
+
+    for nation in NATIONS.iter() {
+        let mut counts: HashMap<Observation, f64> = HashMap::new();
+        let mut cost = 0.0;
+
+        for sample in 0..samples as u64 {
+            let mut sim = state.clone();
+            sim.verbose = false;
+            sim.player.nationality = nation;
+            // Одинаковые зёрна для всех наций: различия в исходах
+            // объясняются только нацией, а не случайностью.
+            *sim.rng = ChaCha8Rng::seed_from_u64(sample);
+
+            sim.advance(&strategy)?;
+
+            *counts.entry(observe(state, &sim)).or_default() += 1.0;
+            cost += min_before - sim.player.min_resource();
+        }
+
+        for count in counts.values_mut() {
+            *count /= samples as f64;
+        }
+        likelihoods.push(counts);
+        costs.push(cost / samples as f64);
+    }
+
+    Ok((OutcomeModel { likelihoods }, costs))
+}
+
+/// Ожидаемый прирост информации (взаимная информация между нацией и
+/// исходом действия) при представлении `belief`.
+pub fn information_gain(belief: &Belief, model: &OutcomeModel) -> f64 {
+    // Сама по себе взаимная информация неотрицательна, но ошибки округления
+    // могут дать что-то вроде `-0.000`.
+    let mut marginal: HashMap<&Observation, f64> = HashMap::new();
+    for (p, likelihood) in belief.probabilities.iter().zip(&model.likelihoods) {
+        for (observation, q) in likelihood {
+            *marginal.entry(observation).or_default() += p * q;
+        }
+    }
+
+    belief
+        .probabilities
+        .iter()
+        .zip(&model.likelihoods)
+        .flat_map(|(&p, likelihood)| {
+            let marginal = &marginal;
+            likelihood
+                .iter()
+                .map(move |(observation, &q)| p * q * (q / marginal[observation]).log2())
+        })
+        .filter(|x| x.is_finite())
+        .sum::<f64>()
+        .max(0.0)
+}
+
+/// Оценивает каждое допустимое действие с точки зрения информации о нации и цены для выживания.
+/// Результат отсортирован по убыванию прироста информации.
+pub fn analyze_actions(
+    state: &GameState,
+    belief: &Belief,
+    samples: usize,
+) -> Result<Vec<ActionValue>, SimError> {
+    let mut values = state
+        .legal_actions()
+        .into_iter()
+        .map(|action| {
+            let (model, costs) = outcome_model(state, action, samples)?;
+            let expected_cost = belief
+                .probabilities
+                .iter()
+                .zip(&costs)
+                .map(|(p, cost)| p * cost)
+                .sum();
+
+            Ok(ActionValue {
+                action,
+                information_gain: information_gain(belief, &model),
+                expected_cost,
+            })
+        })
+        .collect::<Result<Vec<_>, SimError>>()?;
+
+    values.sort_by(|a, b| b.information_gain.total_cmp(&a.information_gain));
+    Ok(values)
+}
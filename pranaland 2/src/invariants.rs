@@ -0,0 +1,254 @@
+//! Проверка целостности состояния игры.
+//!
+//! Проверка включается полем `GameState::check_invariants` (для пакетного
+//! прогона - `BatchConfig::check_invariants`) и выполняется после каждого
+//! `GameState::advance`. Найденные нарушения возвращаются как ошибка
+//! `SimErrorKind::InvariantViolated` с отчётом `InvariantReport`, в котором
+//! сохранено состояние игры перед интервалом - по нему интервал можно
+//! повторить в отладчике (`Replay::run`).
+
+use crate::{
+    definitions::{GameState, SimError, SimErrorKind, Strategy, MAX_LOCATION_HISTORY_LEN},
+    i18n::{text, tr},
+    ids::{FaunaId, LocationId, ResourceId},
+    registry::content,
+    resources::RESOURCES,
+};
+use std::fmt;
+
+/// Нарушение инварианта состояния игры.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Violation {
+    /// Ресурс Игрока стал бесконечным или неопределённым.
+    NonFiniteResource { resource: ResourceId, value: f64 },
+
+    /// Работающей фауны больше, чем живёт в локации по описанию содержимого
+    /// (`Location::fauna`), а эффекты не объявили прибавку
+    /// (`GameState::declare_fauna`). Больше этого не бывает и в мире
+    /// с динамикой фауны.
+    FaunaExceeded {
+        fauna: FaunaId,
+        effective: usize,
+        allowed: usize,
+    },
+
+    /// `location_history` длиннее `MAX_LOCATION_HISTORY_LEN`.
+    HistoryTooLong { len: usize },
+
+    /// `location_history` не заканчивается текущей локацией.
+    HistoryMismatch {
+        last: Option<LocationId>,
+        location: LocationId,
+    },
+
+    /// `time_spent_in_this_location` не согласуется с перемещениями Игрока.
+    TimeInLocation { expected: usize, actual: usize },
+}
+
+/// Всё, что нужно, чтобы повторить интервал, на котором нарушены инварианты.
+#[derive(Clone)]
+pub struct Replay {
+    /// Стратегия, выбиравшая действие на этом интервале.
+    pub strategy: &'static str,
+
+    /// Состояние игры перед интервалом, вместе с генератором случайных чисел.
+    pub state: GameState,
+}
+
+/// Отчёт о нарушении инвариантов.
+#[derive(Debug, Clone)]
+pub struct InvariantReport {
+    pub violations: Vec<Violation>,
+    pub replay: Replay,
+}
+
+impl Replay {
+    /// Повторяет интервал со стратегией `strategy`. Если это та же стратегия,
+    /// что и в первый раз, результат совпадёт - включая ту же ошибку.
+    pub fn run(&self, strategy: &dyn Strategy) -> Result<GameState, SimError> {
+        let mut state = self.state.clone();
+        state.advance(strategy)?;
+        Ok(state)
+    }
+}
+
+/// Проверяет интервал, переведший игру из состояния `before` в `after`.
+pub fn check(
+    before: &GameState,
+    after: &GameState,
+    strategy: &dyn Strategy,
+) -> Result<(), SimError> {
+    let violations = violations(before, after);
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    let report = InvariantReport {
+        violations,
+        replay: Replay {
+            strategy: strategy.name(),
+            state: before.clone(),
+        },
+    };
+    Err(before.error(SimErrorKind::InvariantViolated(Box::new(report))))
+}
+
+/// Все нарушения инвариантов на интервале из `before` в `after`.
+pub fn violations(before: &GameState, after: &GameState) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    for resource in RESOURCES.iter() {
+        let value = after.player.resources[resource.id];
+        if !value.is_finite() {
+            violations.push(Violation::NonFiniteResource {
+                resource: resource.id,
+                value,
+            });
+        }
+    }
+
+    for (fauna, &effective) in &after.effective_fauna {
+        let allowed = after.location.fauna[fauna] + after.declared_fauna[fauna];
+        if effective > allowed {
+            violations.push(Violation::FaunaExceeded {
+                fauna,
+                effective,
+                allowed,
+            });
+        }
+    }
+
+    let len = after.location_history.len();
+    if len > MAX_LOCATION_HISTORY_LEN {
+        violations.push(Violation::HistoryTooLong { len });
+    }
+
+    let last = after.location_history.last().map(|location| location.id);
+    if last != Some(after.location.id) {
+        violations.push(Violation::HistoryMismatch {
+            last,
+            location: after.location.id,
+        });
+    }
+
+    let moved = after.location.id != before.location.id;
+    let expected = if moved {
+        0
+    } else if after.transit.is_some() {
+        before.time_spent_in_this_location
+    } else {
+        before.time_spent_in_this_location + 1
+    };
+    if after.time_spent_in_this_location != expected {
+        violations.push(Violation::TimeInLocation {
+            expected,
+            actual: after.time_spent_in_this_location,
+        });
+    }
+
+    violations
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match *self {
+            Violation::NonFiniteResource { resource, value } => tr!(
+                "invariant.non_finite_resource",
+                resource = resource.name(),
+                value = value
+            ),
+            Violation::FaunaExceeded {
+                fauna,
+                effective,
+                allowed,
+            } => tr!(
+                "invariant.fauna_exceeded",
+                fauna = fauna.name(),
+                effective = effective,
+                allowed = allowed
+            ),
+            Violation::HistoryTooLong { len } => tr!(
+                "invariant.history_too_long",
+                len = len,
+                max = MAX_LOCATION_HISTORY_LEN
+            ),
+            Violation::HistoryMismatch { last, location } => tr!(
+                "invariant.history_mismatch",
+                last = last.map_or("-", LocationId::name),
+                location = location.name()
+            ),
+            Violation::TimeInLocation { expected, actual } => tr!(
+                "invariant.time_in_location",
+                expected = expected,
+                actual = actual
+            ),
+        };
+        write!(f, "{message}")
+    }
+}
+
+/// Наборы содержимого, с которыми шла игра.
+fn packs() -> Vec<&'static str> {
+    content().map(|content| content.packs.clone()).unwrap_or_default()
+}
+
+impl fmt::Debug for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Replay")
+            .field("strategy", &self.strategy)
+            .field("seed", &self.state.seed)
+            .field("step", &self.state.time_passed)
+            .field("nation", &self.state.player.nationality.id)
+            .field("packs", &packs())
+            .field("params", &self.state.params)
+            .field("dynamics", &self.state.world.default_dynamics)
+            .field("calendar", &self.state.world.calendar.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let params: Vec<String> = self
+            .state
+            .params
+            .iter()
+            .map(|(param, value)| format!("{param:?} = {value}"))
+            .collect();
+        write!(
+            f,
+            "{}",
+            tr!(
+                "invariant.replay",
+                seed = self.state.seed,
+                step = self.state.time_passed,
+                strategy = self.strategy,
+                nation = self.state.player.nationality.id,
+                packs = packs().join(", "),
+                params = params.join(", "),
+                dynamics = match self.state.world.default_dynamics {
+                    Some(dynamics) => format!("{dynamics:?}"),
+                    None => text("invariant.no_dynamics").to_string(),
+                },
+                calendar = if self.state.world.calendar.is_some() {
+                    text("invariant.calendar_on")
+                } else {
+                    text("invariant.calendar_off")
+                }
+            )
+        )
+    }
+}
+
+impl fmt::Display for InvariantReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let violations: Vec<String> = self.violations.iter().map(Violation::to_string).collect();
+        write!(
+            f,
+            "{}: {}; {}",
+            text("invariant.report"),
+            violations.join("; "),
+            self.replay
+        )
+    }
+}
@@ -0,0 +1,87 @@
+//! Симулятор жизни в Дурляндии.
+//!
+//! Библиотека описывает правила игры и инструменты для их исследования:
+//!
+//! - [`GameState`] - состояние игры одного Игрока; [`GameState::advance`]
+//!   проводит его через очередной временной интервал;
+//! - [`Strategy`] - стратегия поведения Игрока. Готовые стратегии лежат
+//!   в модуле [`strategies`], а свои достаточно реализовать как этот трейт;
+//! - [`ContentRegistry`] - содержимое игры (локации, нации, расы и т.д.),
+//!   собранное из базового набора [`base_pack`] и модов [`ContentPack`];
+//! - [`run_batch`] - прогон стратегии на множестве случайных игр;
+//! - [`evolve`], [`optimize_robust`] и [`analyze_actions`] - подбор стратегий
+//!   генетическим алгоритмом и оценка ценности информации о своей нации;
+//! - [`World`] - мир, в котором одновременно живут несколько Игроков;
+//! - [`invariants`] - проверка целостности состояния игры для отладки правил;
+//! - [`reports`] - отчёты об исследовании правил, которые строит программа
+//!   `pranaland` из командной строки.
+//!
+//! Примеры модов лежат в `examples/mods.rs` (`cargo run --example mods`).
+//!
+//! Пример своей стратегии - Игрок, который всегда зумбалит на месте:
+//!
+//! ```
+//! use pranaland::{occupations::ZOOMBALING, Action, GameState, Strategy};
+//!
+//! struct Workaholic;
+//!
+//! impl Strategy for Workaholic {
+//!     fn name(&self) -> &'static str {
+//!         "Трудоголик"
+//!     }
+//!
+//!     fn take_action(&self, _state: &mut GameState) -> Action {
+//!         Action {
+//!             destination: None,
+//!             occupation: Some(ZOOMBALING),
+//!         }
+//!     }
+//! }
+//!
+//! let mut state = GameState::new("Жора".to_string(), None, 1337).unwrap();
+//! state.verbose = false;
+//! while state.player.is_alive() && state.advance(&Workaholic).is_ok() {}
+//! assert!(state.time_passed > 0);
+//! ```
+
+pub mod attribution;
+pub mod batch;
+pub mod calendar;
+pub mod definitions;
+pub mod demography;
+pub mod fauna;
+pub mod genetic;
+pub mod i18n;
+pub mod ids;
+pub mod invariants;
+pub mod information;
+pub mod locations;
+pub mod multiplayer;
+pub mod nations;
+pub mod occupations;
+pub mod params;
+pub mod registry;
+pub mod reports;
+pub mod resources;
+pub mod risk;
+pub mod robust;
+pub mod sensitivity;
+pub mod simulation;
+pub mod strategies;
+pub mod survival;
+pub mod trade;
+pub mod travel;
+pub mod world;
+
+pub use batch::{run_batch, BatchConfig, BatchResult};
+pub use definitions::{Action, ActionError, FaunaMap, GameState, Player, ResourceMap, Strategy};
+pub use fauna::{Fauna, FAUNA};
+pub use genetic::{evolve, GeneticConfig};
+pub use ids::{FaunaId, IdMap, OccupationId, ResourceId};
+pub use information::{analyze_actions, Belief};
+pub use invariants::{InvariantReport, Replay};
+pub use multiplayer::World;
+pub use occupations::{Occupation, OCCUPATIONS};
+pub use registry::{base_pack, content, install, ContentError, ContentPack, ContentRegistry};
+pub use resources::{Resource, RESOURCES};
+pub use robust::{optimize_robust, RobustObjective};
@@ -1,206 +1,406 @@
 use crate::definitions::{
-    Fauna::{self, *},
-    FaunaMap,
-    Occupation::*,
-    PostEffect, PreEffect,
-    Resource::*,
+    narrate, EffectSource, FaunaMap, PostEffect, PreEffect, SimError, SimErrorKind,
 };
-use enum_map::enum_map;
+use crate::{
+    calendar::Season::{self, *},
+    fauna::{counts, FaunaBehaviour, CHUCHUNDRA, FAUNA, SISYANDRA, SLESANDRA},
+    i18n::{render, text_or},
+    ids::{BiomeId, FaunaId, LocationId},
+    nations::DROCENTY,
+    params::{default_content_params, default_params, ContentParamMap, Param::{self, *}, ParamMap},
+    registry::{content, missing, ContentKind},
+    occupations::{GOOLBONING, SHLAMSING, ZOOMBALING},
+    resources::{HEALTH, MONEY, SATISFACTION},
+    world::FaunaDynamics,
+};
+use enum_map::{enum_map, EnumMap};
 use lazy_static::lazy_static;
 use rand::seq::SliceRandom;
-use std::fmt;
+use std::{fmt, hash};
+use strum::IntoEnumIterator;
 
-use Biome::*;
+const WORKLAND: BiomeId = BiomeId("workland");
+const BEACHLAND: BiomeId = BiomeId("beachland");
+const PRANALAND: BiomeId = BiomeId("pranaland");
 
-/// То, что в условии называется "Локацией".
-#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
-pub enum Biome {
-    #[strum(to_string = "Воркленд")]
-    Workland,
+/// Биом - то, что в условии называется "Локацией".
+pub struct Biome {
+    pub id: BiomeId,
 
-    #[strum(to_string = "Бичленд")]
-    Beachland,
+    /// Человекочитаемое название биома, например "Воркленд".
+    /// Перевод - в каталоге сообщений по ключу `biome.<id>.name`.
+    pub name: &'static str,
 
-    #[strum(to_string = "Праналенд")]
-    Pranaland,
+    /// Множитель дохода от фауны в каждое время года.
+    pub productivity: EnumMap<Season, f64>,
 }
 
 /// Локация - место, где Игрок может находиться.
 /// То, что в условии называется "Местностью".
 pub struct Location {
+    pub id: LocationId,
+
     /// Человекочитаемое название локации, например "Балбесбург".
+    /// Перевод - в каталоге сообщений по ключу `location.<id>.name`.
     pub name: &'static str,
 
     /// Биом ("Локация" в условии), где находится эта локация.
-    pub biome: Biome,
+    pub biome: BiomeId,
 
-    /// Количество представителей для каждого типа фауны.
+    /// Количество представителей для каждого вида фауны.
     pub fauna: FaunaMap,
 
+    /// Описание эффектов локации. Числа правил в нём не пишутся литералами:
+    /// это шаблон (см. `i18n`), в который подставляются значения параметров
+    /// по их именам, например `{BalbesburgDamage}`, а параметров, объявленных
+    /// содержимым (`ParamDef`), - по идентификаторам.
+    /// Перевод - по ключу `location.<id>.effect`.
     pub effect_description: &'static str,
+
     pub pre_effect: Option<&'static PreEffect>,
     pub post_effect: Option<&'static PostEffect>,
+
+    /// Поведение фауны, проявляющееся только в этой локации.
+    pub fauna_behaviours: Vec<(FaunaId, FaunaBehaviour)>,
+
+    /// Изменение численности фауны со временем, либо `None`, если
+    /// фауна в локации неизменна (или меняется по
+    /// `WorldState::default_dynamics`, если та включена).
+    pub dynamics: Option<FaunaDynamics>,
 }
 
 lazy_static! {
-    static ref WORKLAND_FAUNA: FaunaMap = enum_map! {
-        Slesandra => 3,
-        Sisyandra => 1,
-        Chuchundra => 1,
-    };
-    static ref BEACHLAND_FAUNA: FaunaMap = enum_map! {
-        Slesandra => 1,
-        Sisyandra => 3,
-        Chuchundra => 1,
-    };
-    static ref PRANALAND_FAUNA: FaunaMap = enum_map! {
-        Slesandra => 1,
-        Sisyandra => 1,
-        Chuchundra => 3,
-    };
-    pub static ref LOCATIONS: Vec<Location> = vec![
+    /// Все биомы из установленного содержимого игры (`content()`).
+    pub static ref BIOMES: &'static [Biome] =
+        content().map(|content| content.biomes.as_slice()).unwrap_or_default();
+
+    /// Все локации из установленного содержимого игры (`content()`).
+    pub static ref LOCATIONS: &'static [Location] =
+        content().map(|content| content.locations.as_slice()).unwrap_or_default();
+
+    static ref WORKLAND_FAUNA: FaunaMap =
+        counts(&[(SLESANDRA, 3), (SISYANDRA, 1), (CHUCHUNDRA, 1)]);
+    static ref BEACHLAND_FAUNA: FaunaMap =
+        counts(&[(SLESANDRA, 1), (SISYANDRA, 3), (CHUCHUNDRA, 1)]);
+    static ref PRANALAND_FAUNA: FaunaMap =
+        counts(&[(SLESANDRA, 1), (SISYANDRA, 1), (CHUCHUNDRA, 3)]);
+}
+
+/// Биомы базового набора содержимого.
+pub fn base_biomes() -> Vec<Biome> {
+    vec![
+        Biome {
+            id: WORKLAND,
+            name: "Воркленд",
+            productivity: enum_map! { Spring => 1.0, Summer => 0.8, Autumn => 1.2, Winter => 1.0 },
+        },
+        Biome {
+            id: BEACHLAND,
+            name: "Бичленд",
+            productivity: enum_map! { Spring => 1.0, Summer => 1.3, Autumn => 0.8, Winter => 0.5 },
+        },
+        Biome {
+            id: PRANALAND,
+            name: "Праналенд",
+            productivity: enum_map! { Spring => 1.2, Summer => 1.0, Autumn => 1.0, Winter => 0.8 },
+        },
+    ]
+}
+
+/// Локации базового набора содержимого.
+pub fn base_locations() -> Vec<Location> {
+    vec![
         Location {
+            id: LocationId("balbesburg"),
             name: "Балбесбург",
-            biome: Workland,
-            fauna: *WORKLAND_FAUNA,
-            effect_description: "С вероятноятью 0.15 каждая слесандра может \
-                                 нанести ущерб здоровью в размере 0.1 единицы.",
+            biome: WORKLAND,
+            fauna: WORKLAND_FAUNA.clone(),
+            effect_description: "С вероятностью {BalbesburgDamageChance} каждая слесандра \
+                                 может нанести ущерб здоровью в размере \
+                                 {BalbesburgDamage} единицы.",
             pre_effect: None,
-            post_effect: Some(&|state, _action| {
-                const DAMAGE: f64 = 0.1;
-                for i in 0..state.effective_fauna[Slesandra] {
-                    if state.chance(0.15) {
-                        println!("Слесандра №{} причиняет вред здоровью нашего героя в размере {DAMAGE:.1} ед.", i + 1);
-                        state.resource_change[Health] -= 0.1;
-                    }
-                }
-            })
+            post_effect: None,
+            fauna_behaviours: vec![(
+                SLESANDRA,
+                FaunaBehaviour::Aggressive {
+                    resource: HEALTH,
+                    chance: BalbesburgDamageChance,
+                    damage: BalbesburgDamage,
+                },
+            )],
+            dynamics: None,
         },
         Location {
+            id: LocationId("dolbesburg"),
             name: "Долбесбург",
-            biome: Workland,
-            fauna: *WORKLAND_FAUNA,
-            effect_description: "Добавляет 20% к производительности слесандр, \
-                                 но забирает на 30% больше удовлетворенности.",
+            biome: WORKLAND,
+            fauna: WORKLAND_FAUNA.clone(),
+            effect_description: "Добавляет {DolbesburgMoney:+%}% к производительности \
+                                 слесандр, но забирает на {DolbesburgSatisfaction:+%}% \
+                                 больше удовлетворенности.",
             pre_effect: None,
             post_effect: Some(&|state, action| {
-                if action.occupation == Some(Zoombaling) {
-                    state.resource_change[Money] *= 1.2;
-                    state.resource_change[Satisfaction] *= 1.3;
-                    assert!(state.resource_change[Satisfaction] <= 0.0);
+                if action.occupation == Some(ZOOMBALING) {
+                    state.resource_change[MONEY] *= state.params[DolbesburgMoney];
+                    state.resource_change[SATISFACTION] *= state.params[DolbesburgSatisfaction];
+                    // Зумбалить всегда стоит удовлетворённости, как бы ни были
+                    // заданы параметры и занятия.
+                    let change = state.resource_change[SATISFACTION];
+                    if change > 0.0 {
+                        return Err(state.error(SimErrorKind::WrongDirection {
+                            source: EffectSource::Location(state.location.id),
+                            resource: SATISFACTION,
+                            change,
+                        }));
+                    }
                 }
-            })
+                Ok(())
+            }),
+            fauna_behaviours: vec![],
+            dynamics: None,
         },
         Location {
+            id: LocationId("kuramariby"),
             name: "Курамарибы",
-            biome: Beachland,
-            fauna: *BEACHLAND_FAUNA,
-            effect_description: "Каждая сисяндра перестает работать с вероятностью 0.7 \
-                                 во втором и последующих интервалах нахождения в локации.",
+            biome: BEACHLAND,
+            fauna: BEACHLAND_FAUNA.clone(),
+            effect_description: "Каждая сисяндра перестает работать с вероятностью \
+                                 {KuramaribyStrikeChance} во втором и последующих \
+                                 интервалах нахождения в локации.",
             pre_effect: Some(&|state| {
                 if state.time_spent_in_this_location >= 1 {
-                    let sis_count = state.effective_fauna[Sisyandra];
+                    let sis_count = state.effective_fauna[SISYANDRA];
                     for _ in 0..sis_count {
-                        if state.chance(0.7) {
-                            state.effective_fauna[Sisyandra] -= 1;
-                            println!(
-                                "Одна из сисяндр перестаёт работать (осталось {}).",
-                                state.effective_fauna[Sisyandra]
+                        if state.chance(state.params[KuramaribyStrikeChance]) {
+                            state.effective_fauna[SISYANDRA] -= 1;
+                            narrate!(
+                                state,
+                                "kuramariby.strike",
+                                left = state.effective_fauna[SISYANDRA]
                             );
                         }
                     }
                 }
+                Ok(())
             }),
             post_effect: None,
+            fauna_behaviours: vec![],
+            dynamics: None,
         },
         Location {
+            id: LocationId("punta-pelikana"),
             name: "Пунта-пеликана",
-            biome: Beachland,
-            fauna: *BEACHLAND_FAUNA,
+            biome: BEACHLAND,
+            fauna: BEACHLAND_FAUNA.clone(),
             effect_description: "Начиная со 2 интервала нахождения в локации, сисяндры \
-                                 генерируют на 23% больше удовлетворенности, но с \
-                                 вероятностью 0.2 списывается 50% ВСЕХ денег.",
+                                 генерируют на {PuntaPelikanaSatisfaction:+%}% больше \
+                                 удовлетворенности, но с вероятностью \
+                                 {PuntaPelikanaCasinoChance} списывается \
+                                 {PuntaPelikanaCasinoLoss:%}% ВСЕХ денег.",
             pre_effect: None,
             post_effect: Some(&|state, action| {
                 if state.time_spent_in_this_location >= 1 {
-                    if action.occupation == Some(Goolboning) {
-                        state.resource_change[Satisfaction] *= 1.23;
+                    if action.occupation == Some(GOOLBONING) {
+                        state.resource_change[SATISFACTION] *= state.params[PuntaPelikanaSatisfaction];
                     }
 
-                    if state.chance(0.2) {
-                        let money_lost = state.player.resources[Money] * 0.5; 
-                        state.resource_change[Money] -= money_lost;
+                    if state.chance(state.params[PuntaPelikanaCasinoChance]) {
+                        let money_lost = state.player.resources[MONEY] * state.params[PuntaPelikanaCasinoLoss];
+                        state.resource_change[MONEY] -= money_lost;
 
-                        println!(
-                            "{} проигрывает в казино и теряет половину денег! ({:.2})",
-                            state.player.name,
-                            -money_lost
+                        narrate!(
+                            state,
+                            "punta_pelikana.casino",
+                            name = state.player.name,
+                            lost = -money_lost
                         );
                     }
                 }
-            })
+                Ok(())
+            }),
+            fauna_behaviours: vec![],
+            dynamics: None,
         },
         Location {
+            id: LocationId("shrinavas"),
             name: "Шринавас",
-            biome: Pranaland,
-            fauna: *PRANALAND_FAUNA,
-            effect_description: "Добавляет 13 процентов к производительности чучундр.",
+            biome: PRANALAND,
+            fauna: PRANALAND_FAUNA.clone(),
+            effect_description: "Добавляет {ShrinavasHealth:+%} процентов к \
+                                 производительности чучундр.",
             pre_effect: None,
             post_effect: Some(&|state, action| {
-                if action.occupation == Some(Shlamsing) {
-                    state.resource_change[Health] *= 1.13;
+                if action.occupation == Some(SHLAMSING) {
+                    state.resource_change[HEALTH] *= state.params[ShrinavasHealth];
                 }
-            })
+                Ok(())
+            }),
+            fauna_behaviours: vec![],
+            dynamics: None,
         },
         Location {
+            id: LocationId("hare-kirishi"),
             name: "Харе-Кириши",
-            biome: Pranaland,
-            fauna: *PRANALAND_FAUNA,
+            biome: PRANALAND,
+            fauna: PRANALAND_FAUNA.clone(),
             effect_description: "При попадании Дроцентов они расходуют дополнительно \
-                                 по 10% здоровья за каждый интервал.",
+                                 по {HareKirishiDamage:%}% здоровья за каждый интервал.",
             pre_effect: None,
             post_effect: Some(&|state, _action| {
-                if state.player.nationality.name_singular == "Дроцент" {
-                    const PENALTY: f64 = 0.1;
-                    let damage = state.player.resources[Health] * 0.1;
-                    state.resource_change[Health] -= damage;
-
-                    println!(
-                        "{} - {}, и, пока находится в {}, получает урон в размере {PENALTY:.2} от своего здоровья: {:.2}.",
-                        state.player.name,
-                        state.player.nationality.name_singular,
-                        state.location.name,
-                        damage
+                if state.player.nationality.id == DROCENTY {
+                    let penalty = state.params[HareKirishiDamage];
+                    let damage = state.player.resources[HEALTH] * penalty;
+                    state.resource_change[HEALTH] -= damage;
+
+                    narrate!(
+                        state,
+                        "hare_kirishi.damage",
+                        name = state.player.name,
+                        nation = state.player.nationality.name_singular(),
+                        location = state.location.name(),
+                        penalty = penalty,
+                        damage = damage
                     );
                 }
-            })
+                Ok(())
+            }),
+            fauna_behaviours: vec![],
+            dynamics: None,
         },
-    ];
+    ]
 }
 
-pub fn random_location(rng: &mut impl rand::Rng) -> &'static Location {
-    LOCATIONS
-        .choose(rng)
-        .expect("LOCATIONS Vec can't be empty!")
+pub fn random_location(rng: &mut impl rand::Rng) -> Result<&'static Location, SimError> {
+    LOCATIONS.choose(rng).ok_or_else(|| missing(ContentKind::Location))
+}
+
+pub fn find_location(name: &str) -> Option<&'static Location> {
+    LOCATIONS.iter().find(|loc| loc.name == name)
+}
+
+impl Biome {
+    /// Название биома на текущем языке.
+    pub fn name(&self) -> &'static str {
+        text_or(&format!("biome.{}.name", self.id), self.name)
+    }
 }
 
 impl Location {
-    pub fn count(&self, fauna: Fauna) -> usize {
+    /// Название локации на текущем языке.
+    pub fn name(&self) -> &'static str {
+        text_or(&format!("location.{}.name", self.id), self.name)
+    }
+
+    /// Описание эффектов локации на текущем языке при значениях
+    /// параметров правил `params` и `content_params`.
+    pub fn effect_description(&self, params: &ParamMap, content_params: &ContentParamMap) -> String {
+        let template = text_or(&format!("location.{}.effect", self.id), self.effect_description);
+        let names: Vec<String> = Param::iter().map(|param| format!("{param:?}")).collect();
+        let args: Vec<(&str, &dyn fmt::Display)> = names
+            .iter()
+            .zip(params.values())
+            .map(|(name, value)| (name.as_str(), value as &dyn fmt::Display))
+            .chain(
+                content_params
+                    .iter()
+                    .map(|(id, value)| (id.0, value as &dyn fmt::Display)),
+            )
+            .collect();
+        render(template, &args)
+    }
+
+    /// Название, биом, фауна и описание эффектов локации при значениях
+    /// параметров правил `params` и `content_params`.
+    pub fn describe(&self, params: &ParamMap, content_params: &ContentParamMap) -> String {
+        let fauna: Vec<String> = FAUNA
+            .iter()
+            .map(|fauna| format!("{}: {}", fauna.name(), self.count(fauna.id)))
+            .collect();
+        format!(
+            "{} ({}): {}\n{}",
+            self.name(),
+            self.biome.name(),
+            fauna.join(", "),
+            self.effect_description(params, content_params)
+        )
+    }
+
+    pub fn count(&self, fauna: FaunaId) -> usize {
         self.fauna[fauna]
     }
+
+    /// Порядковый номер локации в `LOCATIONS`. Ошибка - если локации
+    /// нет в установленном содержимом игры.
+    pub fn index(&self) -> Result<usize, SimError> {
+        LOCATIONS
+            .iter()
+            .position(|loc| loc.id == self.id)
+            .ok_or_else(|| {
+                SimError::new(SimErrorKind::UnknownId {
+                    kind: ContentKind::Location,
+                    id: self.id.to_string(),
+                })
+            })
+    }
 }
 
+impl LocationId {
+    pub fn try_get(self) -> Result<&'static Location, SimError> {
+        LOCATIONS.iter().find(|loc| loc.id == self).ok_or_else(|| {
+            SimError::new(SimErrorKind::UnknownId {
+                kind: ContentKind::Location,
+                id: self.to_string(),
+            })
+        })
+    }
+
+    /// Название локации на текущем языке, а если такой локации нет
+    /// в содержимом игры - её идентификатор.
+    pub fn name(self) -> &'static str {
+        self.try_get().map_or(self.0, Location::name)
+    }
+
+    /// Идентификатор локации с ключом `key`, если такая локация есть.
+    pub fn parse(key: &str) -> Option<Self> {
+        LOCATIONS.iter().find(|loc| loc.id.0 == key).map(|loc| loc.id)
+    }
+}
+
+impl BiomeId {
+    pub fn try_get(self) -> Result<&'static Biome, SimError> {
+        BIOMES.iter().find(|biome| biome.id == self).ok_or_else(|| {
+            SimError::new(SimErrorKind::UnknownId {
+                kind: ContentKind::Biome,
+                id: self.to_string(),
+            })
+        })
+    }
+
+    /// Название биома на текущем языке, а если такого биома нет
+    /// в содержимом игры - его идентификатор.
+    pub fn name(self) -> &'static str {
+        self.try_get().map_or(self.0, Biome::name)
+    }
+}
+
+impl PartialEq for Location {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Location {}
+
+impl hash::Hash for Location {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Локация с описанием эффектов при параметрах из условия задачи
+/// (см. `Location::describe`).
 impl fmt::Display for Location {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{} ({}): {Slesandra}: {}, {Sisyandra}: {}, {Chuchundra}: {}\n{}",
-            self.name,
-            self.biome,
-            self.count(Slesandra),
-            self.count(Sisyandra),
-            self.count(Chuchundra),
-            self.effect_description
-        )
+        f.write_str(&self.describe(&default_params(), &default_content_params()))
     }
 }
@@ -1,54 +1,127 @@
-#![allow(dead_code)]
+//! Интерактивная симуляция и отчёты из модуля `pranaland::reports`.
 
-use crate::{definitions::Strategy, nations::find_nation, strategies::*};
-use definitions::GameState;
-
-mod definitions;
-mod locations;
-mod nations;
-mod simulation;
-mod strategies;
+use pranaland::{
+    definitions::{GameState, Strategy},
+    i18n::{set_locale, text, tr, Locale},
+    ids::NationId,
+    reports::*,
+    risk::Objective,
+    robust::RobustObjective,
+    strategies::*,
+};
+use std::{error::Error, io, path::Path};
 
 fn main() {
+    if let Ok(lang) = std::env::var("PRANALAND_LANG") {
+        match lang.parse::<Locale>() {
+            Ok(locale) => set_locale(locale),
+            Err(_) => eprintln!("Unknown PRANALAND_LANG={lang}, expected \"ru\" or \"en\"."),
+        }
+    }
+
+    let args: Vec<String> = std::env::args().collect();
+    let objective = parse_objective(args.get(2).map(String::as_str));
+
+    let out = &mut io::stdout();
+    let report = match args.get(1).map(String::as_str) {
+        Some("evolve") => evolve_strategies(out, objective),
+        Some("robust") => evolve_robust_strategy(out, RobustObjective::Mean, objective),
+        Some("robust-worst") => evolve_robust_strategy(out, RobustObjective::WorstCase, objective),
+        Some("voi") => tabulate_information_value(out),
+        Some("survival") => report_survival(out, Path::new("survival")),
+        Some("attribution") => report_death_attribution(out),
+        Some("sensitivity") => report_sensitivity(out, false),
+        Some("sensitivity-reoptimize") => report_sensitivity(out, true),
+        Some("crowd") => report_crowding(out),
+        Some("trade") => report_trading(out),
+        Some("demography") => report_demography(out, Path::new("demography.csv")),
+        Some("check") => match check_invariants(out) {
+            Ok(0) => Ok(()),
+            Ok(_) => std::process::exit(1),
+            Err(error) => Err(error.into()),
+        },
+        _ => play(),
+    };
+    report.unwrap_or_else(|error: Box<dyn Error>| exit(error));
+}
+
+/// Интерактивная симуляция жизни одного Игрока.
+fn play() -> Result<(), Box<dyn Error>> {
     // let nation = None;
-    let nation = Some(find_nation("Просветлённый").unwrap());
+    let nation = Some(NationId("prosvetlyonnye").try_get()?);
 
-    let mut state = GameState::new("Жора".to_string(), nation, 1337);
+    let mut state = GameState::new("Жора".to_string(), nation, 1337)?;
 
     // let strategy = ApatheticStrategy;
     let strategy = RandomStrategy;
-    println!("Используется {}.", strategy.name());
+    println!("{}", tr!("play.strategy", strategy = strategy.name()));
 
     while state.player.is_alive() {
         println!(
             "{:-^80}",
-            format!(" Интервал времени №{} ", state.time_passed + 1)
+            format!(" {} ", tr!("play.interval", n = state.time_passed + 1))
         );
         println!("{}", state.player);
-        println!("Текущая локация: {}", state.location.name);
+        println!("{}", tr!("play.location", location = state.location.name()));
+        if let Some(calendar) = &state.world.calendar {
+            println!("{}", tr!("play.season", calendar = calendar));
+        }
+
+        if let Err(err) = state.advance(&strategy) {
+            let message = match err.action_error() {
+                Some(error) => tr!("play.illegal_action", name = state.player.name, error = error),
+                None => tr!("play.error", error = err),
+            };
+            println!("{message}");
+            break;
+        }
 
-        state.advance(&strategy);
+        if pause().is_err() {
+            break;
+        }
+    }
 
-        pause();
+    if state.player.is_dead() {
+        println!(
+            "{}",
+            tr!("play.dead", name = state.player.name, n = state.time_passed)
+        );
     }
+    Ok(())
+}
+
+/// Критерий оптимизации стратегий из аргумента командной строки.
+fn parse_objective(arg: Option<&str>) -> Objective {
+    match arg {
+        Some("ruin") => Objective::ProbabilityOfRuin {
+            threshold: 2.0,
+            horizon: 100,
+        },
+        Some("cvar") => Objective::CVaR { alpha: 0.1 },
+        Some("averse") => Objective::RiskAverse { lambda: 0.05 },
+        _ => Objective::MeanSurvival,
+    }
+}
 
-    println!(
-        "{} мёртв :о(\nОн прожил {} временных интервалов.",
-        state.player.name, state.time_passed
-    );
+/// Сообщает об ошибке, из-за которой отчёт невозможно построить,
+/// и завершает программу.
+fn exit(error: impl std::fmt::Display) -> ! {
+    eprintln!("{}", tr!("report.error", error = error));
+    std::process::exit(1)
 }
 
 // https://users.rust-lang.org/t/rusts-equivalent-of-cs-system-pause/4494/4
-fn pause() {
-    use std::io::{self, Read, Write};
+fn pause() -> io::Result<()> {
+    use std::io::{Read, Write};
 
     let mut stdin = io::stdin();
     let mut stdout = io::stdout();
 
     // We want the cursor to stay at the end of the line, so we print without a newline and flush manually.
-    write!(stdout, "Нажмите любую клавишу...").unwrap();
-    stdout.flush().unwrap();
+    write!(stdout, "{}", text("play.pause"))?;
+    stdout.flush()?;
 
     // Read a single byte and discard
-    let _ = stdin.read(&mut [0u8]).unwrap();
+    let _ = stdin.read(&mut [0u8])?;
+    Ok(())
 }
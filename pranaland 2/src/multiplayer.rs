@@ -0,0 +1,229 @@
+use crate::{
+    demography::{Census, Demography},
+    definitions::{Action, GameState, SimError, SimErrorKind, Strategy},
+    i18n::tr,
+    ids::OccupationId,
+    nations::Nation,
+    strategies::FixedActionStrategy,
+    trade::Market,
+    travel::find_route,
+    world::WorldState,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::HashMap;
+
+/// Житель мира - Игрок со своей стратегией.
+pub struct Inhabitant {
+    pub state: GameState,
+    pub strategy: Box<dyn Strategy>,
+
+    /// Ошибки, из-за которых житель бездельничал вместо выбранного действия:
+    /// недопустимые действия и паники стратегии, а также занятия, фауну
+    /// для которых истощили соседи.
+    pub errors: Vec<SimError>,
+}
+
+/// Мир, в котором живёт несколько Игроков. У каждого своя нация и стратегия,
+/// но локации и фауна в них общие.
+pub struct World {
+    pub inhabitants: Vec<Inhabitant>,
+
+    /// Численность фауны во всех локациях, общая для всех жителей.
+    pub world: WorldState,
+
+    /// Генератор, из которого берутся зёрна для генераторов жителей
+    /// и случайности самого мира (миграция фауны).
+    pub rng: ChaCha8Rng,
+
+    /// Количество прошедших временных интервалов.
+    pub time_passed: usize,
+
+    /// Правила рождения новых жителей, либо `None`, если новые жители
+    /// не появляются.
+    pub demography: Option<Demography>,
+
+    /// Переписи населения в конце каждого временного интервала.
+    pub census: Vec<Census>,
+
+    /// Рынки, на которых жители обмениваются ресурсами.
+    pub market: Market,
+}
+
+impl World {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            inhabitants: Vec::new(),
+            world: WorldState::new(),
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            time_passed: 0,
+            demography: None,
+            census: Vec::new(),
+            market: Market::new(),
+        }
+    }
+
+    /// Заселяет в мир нового жителя в случайной локации и возвращает его
+    /// состояние. Если `nation == None`, его нация выбирается случайно.
+    pub fn add_inhabitant(
+        &mut self,
+        name: String,
+        nation: Option<&'static Nation>,
+        strategy: Box<dyn Strategy>,
+    ) -> Result<&mut GameState, SimError> {
+        let mut state = GameState::new(name, nation, self.rng.gen())?;
+        state.verbose = false;
+        state.steps_world = false;
+        state.effective_fauna = self.world.fauna_in(state.location)?;
+
+        let index = self.inhabitants.len();
+        self.inhabitants.push(Inhabitant {
+            state,
+            strategy,
+            errors: Vec::new(),
+        });
+        Ok(&mut self.inhabitants[index].state)
+    }
+
+    /// Живые жители.
+    pub fn alive(&self) -> impl Iterator<Item = &Inhabitant> {
+        self.inhabitants
+            .iter()
+            .filter(|inhabitant| inhabitant.state.player.is_alive())
+    }
+
+    /// Один временной интервал для всех живых жителей.
+    ///
+    /// Сначала жители торгуют, потом все выбирают действия, затем для каждой пары
+    /// "локация × занятие" считается, сколько жителей делят её фауну,
+    /// и только после этого действия выполняются.
+    ///
+    /// Житель, чья стратегия выбрала недопустимое действие или запаниковала,
+    /// теряет интервал, а ошибка записывается в `Inhabitant::errors`.
+    /// Ошибки правил прерывают весь мир.
+    pub fn advance(&mut self) -> Result<(), SimError> {
+        self.trade();
+
+        let idle = Action {
+            destination: None,
+            occupation: None,
+        };
+        let plans: Vec<Option<Action>> = self
+            .inhabitants
+            .iter_mut()
+            .map(|inhabitant| {
+                let state = &mut inhabitant.state;
+                if state.player.is_dead() {
+                    return None;
+                }
+                state.world.clone_from(&self.world);
+                match state.transit {
+                    Some(transit) if transit.remaining == 0 => Some(transit.action),
+                    Some(_) => None,
+                    None => {
+                        let chosen = state
+                            .choose_action(inhabitant.strategy.as_ref())
+                            .and_then(|action| match state.validate_action(&action) {
+                                Ok(_) => Ok(action),
+                                Err(error) => {
+                                    Err(state.error(SimErrorKind::IllegalAction(error)))
+                                }
+                            });
+                        // Стратегия, выбравшая недопустимое действие, просто
+                        // теряет интервал - остальные жители не должны страдать.
+                        Some(chosen.unwrap_or_else(|error| {
+                            inhabitant.errors.push(error);
+                            idle
+                        }))
+                    }
+                }
+            })
+            .collect();
+
+        let mut crowds: HashMap<(usize, OccupationId), usize> = HashMap::new();
+        for (inhabitant, plan) in self.inhabitants.iter().zip(&plans) {
+            if let Some(key) = plan.and_then(|action| crowd_key(&inhabitant.state, &action)) {
+                *crowds.entry(key).or_default() += 1;
+            }
+        }
+
+        for (inhabitant, plan) in self.inhabitants.iter_mut().zip(&plans) {
+            let state = &mut inhabitant.state;
+            if state.player.is_dead() {
+                continue;
+            }
+
+            let action = plan.unwrap_or(idle);
+            let key = crowd_key(state, &action);
+            state.crowding = key.map_or(1, |key| crowds[&key]);
+
+            std::mem::swap(&mut state.world, &mut self.world);
+            let result = match state.advance(&FixedActionStrategy { action }) {
+                Err(error) if error.action_error().is_some() => {
+                    // Пока до жителя дошла очередь, соседи могли истощить фауну,
+                    // ради которой он сюда шёл. Тогда он бездельничает, и его
+                    // доля фауны достаётся тем, кто ещё не работал.
+                    inhabitant.errors.push(error);
+                    if let Some(crowd) = key.and_then(|key| crowds.get_mut(&key)) {
+                        *crowd -= 1;
+                    }
+                    state.crowding = 1;
+                    state.advance(&FixedActionStrategy { action: idle })
+                }
+                result => result,
+            };
+            std::mem::swap(&mut state.world, &mut self.world);
+            result?;
+        }
+
+        self.world.step(&mut self.rng);
+        self.time_passed += 1;
+
+        self.give_births()?;
+        self.census.push(self.take_census()?);
+        Ok(())
+    }
+}
+
+/// Локация и занятие, за фауну которых житель будет конкурировать с
+/// остальными, если совершит действие `action`.
+fn crowd_key(state: &GameState, action: &Action) -> Option<(usize, OccupationId)> {
+    if state.transit.is_some_and(|transit| transit.remaining > 0) {
+        return None;
+    }
+    let location = action.destination.unwrap_or(state.location);
+    if state.transit.is_none()
+        && find_route(state.location, location).is_some_and(|route| route.extra_intervals > 0)
+    {
+        // Игрок только отправляется в дальнюю дорогу и на этом интервале не работает.
+        return None;
+    }
+    // В локации вне содержимого игры конкурировать не с кем: такое действие
+    // всё равно закончится ошибкой.
+    let index = location.index().ok()?;
+    action.occupation.map(|occupation| (index, occupation))
+}
+
+/// Прогоняет мир, в котором живут Игроки со стратегиями `strategies`
+/// (по одному на стратегию, нации случайные), пока все не погибнут или не
+/// пройдёт `max_steps` интервалов. Возвращает, сколько прожил каждый житель.
+pub fn run_world(
+    strategies: Vec<Box<dyn Strategy>>,
+    seed: u64,
+    max_steps: usize,
+) -> Result<Vec<usize>, SimError> {
+    let mut world = World::new(seed);
+    for (i, strategy) in strategies.into_iter().enumerate() {
+        world.add_inhabitant(tr!("world.inhabitant", n = i + 1), None, strategy)?;
+    }
+
+    while world.alive().next().is_some() && world.time_passed < max_steps {
+        world.advance()?;
+    }
+
+    Ok(world
+        .inhabitants
+        .iter()
+        .map(|inhabitant| inhabitant.state.time_passed)
+        .collect())
+}
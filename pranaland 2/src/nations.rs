@@ -1,29 +1,46 @@
-use crate::definitions::*;
+use crate::{
+    definitions::*,
+    fauna::{CHUCHUNDRA, SISYANDRA, SLESANDRA},
+    i18n::text_or,
+    ids::{NationId, RaceId},
+    params::Param::*,
+    registry::{content, missing, ContentKind},
+    occupations::{GOOLBONING, SHLAMSING, ZOOMBALING},
+    resources::{HEALTH, MONEY, SATISFACTION},
+};
 use lazy_static::lazy_static;
 use rand::seq::SliceRandom;
+use std::{fmt, hash};
 
-use Fauna::*;
-use Occupation::*;
-use Race::*;
-use Resource::*;
-
-/// Расы разумных существ.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Race {
-    Shlendrick,
-    Hipstick,
-    Scoofick,
+const SHLENDRICK: RaceId = RaceId("shlendrick");
+const HIPSTICK: RaceId = RaceId("hipstick");
+const SCOOFICK: RaceId = RaceId("scoofick");
+
+/// Дроценты, на которых действует эффект Харе-Кириши.
+pub const DROCENTY: NationId = NationId("drocenty");
+
+/// Раса разумных существ.
+pub struct Race {
+    pub id: RaceId,
+
+    /// Название расы во множественном числе ("Шлендрики").
+    /// Перевод - в каталоге сообщений по ключу `race.<id>.name`.
+    pub name: &'static str,
 }
 
 /// Нации разумных существ.
 pub struct Nation {
+    pub id: NationId,
+
     /// Раса.
-    pub race: Race,
+    pub race: RaceId,
 
     /// Название нации во множественном числе ("Шведы").
+    /// Перевод - в каталоге сообщений по ключу `nation.<id>.plural`.
     pub name_plural: &'static str,
 
     /// Название нации в единственном числе ("Швед").
+    /// Перевод - в каталоге сообщений по ключу `nation.<id>.singular`.
     pub name_singular: &'static str,
 
     /// Эффект, применяющийся на каждом временном интервале
@@ -36,176 +53,335 @@ pub struct Nation {
 }
 
 lazy_static! {
-    pub static ref NATIONS: Vec<Nation> = vec![
+    /// Все расы из установленного содержимого игры (`content()`).
+    pub static ref RACES: &'static [Race] =
+        content().map(|content| content.races.as_slice()).unwrap_or_default();
+
+    /// Все нации из установленного содержимого игры (`content()`).
+    pub static ref NATIONS: &'static [Nation] =
+        content().map(|content| content.nations.as_slice()).unwrap_or_default();
+}
+
+/// Расы базового набора содержимого.
+pub fn base_races() -> Vec<Race> {
+    vec![
+        Race {
+            id: SHLENDRICK,
+            name: "Шлендрики",
+        },
+        Race {
+            id: HIPSTICK,
+            name: "Хипстики",
+        },
+        Race {
+            id: SCOOFICK,
+            name: "Скуфики",
+        },
+    ]
+}
+
+/// Нации базового набора содержимого.
+pub fn base_nations() -> Vec<Nation> {
+    vec![
         Nation {
+            id: NationId("mozhory"),
             name_plural: "Можоры",
             name_singular: "Можор",
-            race: Shlendrick,
+            race: SHLENDRICK,
             pre_effect: None,
             post_effect: Some(&|state, action| {
-                // При гульбонстве тратят на 23%  больше денег по сравнению с остальными,
-                // зато при зумбальстве в одном случае из 3 вообще не расходуют здоровье.
+                // При гульбонстве тратят больше денег по сравнению с остальными
+                // (`MozhoryGoolboningMoney`, по условию на 23%), зато при зумбальстве
+                // с вероятностью `MozhoryShirkChance` (по условию 1/3) вообще не расходуют здоровье.
                 match action.occupation {
-                    Some(Goolboning) => {
-                        println!(
-                            "Как {}, {} расходует больше денег на гульбонство.",
-                            state.player.name,
-                            state.player.nationality.name_singular
+                    Some(GOOLBONING) => {
+                        narrate!(
+                            state,
+                            "mozhory.goolboning",
+                            name = state.player.name,
+                            nation = state.player.nationality.name_singular()
                         );
-                        state.resource_change[Money] *= 1.23;
+                        state.resource_change[MONEY] *= state.params[MozhoryGoolboningMoney];
                     }
-                    Some(Zoombaling) => {
-                        if state.chance(1.0 / 3.0) {
-                            println!(
-                                "{} умудряется уклониться от работы и не потратить своё здоровье! ({})",
-                                state.player.name,
-                                state.player.nationality.name_singular
-                            );
-                            state.resource_change[Health] = 0.0;
-                        }
+                    Some(ZOOMBALING) if state.chance(state.params[MozhoryShirkChance]) => {
+                        narrate!(
+                            state,
+                            "mozhory.shirk",
+                            name = state.player.name,
+                            nation = state.player.nationality.name_singular()
+                        );
+                        state.resource_change[HEALTH] = 0.0;
                     }
                     _ => {},
                 }
+                Ok(())
             }),
         },
         Nation {
+            id: NationId("nischeborody"),
             name_plural: "Нищебороды",
             name_singular: "Нищебород",
-            race: Shlendrick,
+            race: SHLENDRICK,
             pre_effect: None,
             post_effect: Some(&|state, action| {
-                // При гульбонстве тратят на 87% меньше денег,
-                // но на 76% больше здоровья.
-                if action.occupation == Some(Goolboning) {
-                    println!(
-                        "Так как {} - {}, он тратит гораздо меньше денег и гораздо больше здоровья на гульбонство.",
-                        state.player.name,
-                        state.player.nationality.name_singular
+                // При гульбонстве тратят меньше денег (`NischeborodyGoolboningMoney`,
+                // по условию на 87%), но больше здоровья (`NischeborodyGoolboningHealth`,
+                // по условию на 76%).
+                if action.occupation == Some(GOOLBONING) {
+                    narrate!(
+                        state,
+                        "nischeborody.goolboning",
+                        name = state.player.name,
+                        nation = state.player.nationality.name_singular()
                     );
-                    state.resource_change[Money] *= 1.0 - 0.87;
-                    state.resource_change[Health] *= 1.76;
+                    state.resource_change[MONEY] *= state.params[NischeborodyGoolboningMoney];
+                    state.resource_change[HEALTH] *= state.params[NischeborodyGoolboningHealth];
                 }
+                Ok(())
             }),
         },
         Nation {
+            id: NationId("soevye"),
             name_plural: "Соевые",
             name_singular: "Соевый",
-            race: Hipstick,
+            race: HIPSTICK,
             pre_effect: None,
             post_effect: Some(&|state, action| {
                 // Крайне тяжело переносят зумбальство, затрачивая дополнительно
-                // 0.12 единиц здоровья на каждую чучундру (???) в локации.
-                if action.occupation == Some(Zoombaling) {
-                    const PENALTY: f64 = 0.12;
-                    let chuch_count = state.effective_fauna[Chuchundra];
-                    let change = chuch_count as f64 * PENALTY;
-                    println!(
-                        "Как {}, {} плохо переносит зумбальство и тратит дополнительно по {} ед. здоровья \
-                        на каждую чучундру ({}) в локации ({}).",
-                        state.player.nationality.name_singular,
-                        state.player.name,
-                        PENALTY,
-                        chuch_count,
-                        -change
+                // `SoyChuchundraPenalty` (по условию 0.12) единиц здоровья
+                // на каждую чучундру (???) в локации.
+                if action.occupation == Some(ZOOMBALING) {
+                    let penalty = state.params[SoyChuchundraPenalty];
+                    let chuch_count = state.effective_fauna[CHUCHUNDRA];
+                    let change = chuch_count as f64 * penalty;
+                    narrate!(
+                        state,
+                        "soevye.zoombaling",
+                        name = state.player.name,
+                        nation = state.player.nationality.name_singular(),
+                        penalty = penalty,
+                        count = chuch_count,
+                        change = -change
                     );
-                    state.resource_change[Health] -= change;
+                    state.resource_change[HEALTH] -= change;
                 }
+                Ok(())
             }),
         },
         Nation {
+            id: NationId("prosvetlyonnye"),
             name_plural: "Просветлённые",
             name_singular: "Просветлённый",
-            race: Hipstick,
+            race: HIPSTICK,
             pre_effect: None,
             post_effect: Some(&|state, action| {
                 // Во время шлямсания могут получить дополнтельную удовлетворенность жизнью в количестве,
-                // равном количеству сисяндр в последних 3 локациях, умноженному на 0.31.
+                // равном количеству сисяндр в последних 3 локациях, умноженному на
+                // `ProsvetlyonnyeSisyandraBonus` (по условию 0.31).
                 const N: usize = 3;
-                const MULTIPLIER: f64 = 0.31;
+                let multiplier = state.params[ProsvetlyonnyeSisyandraBonus];
 
-                if action.occupation == Some(Shlamsing) {
+                if action.occupation == Some(SHLAMSING) {
                     let loc_iter = state.location_history.iter()
                         .rev()
                         .take(N);
 
                     let sis_count: usize = loc_iter
                         .clone()
-                        .map(|loc| loc.count(Sisyandra))
+                        .map(|loc| loc.count(SISYANDRA))
                         .sum();
 
-                    let satisfaction_bonus = sis_count as f64 * 0.31;
+                    let satisfaction_bonus = sis_count as f64 * multiplier;
 
-                    println!(
-                        "{} - {}, и поэтому во время шлямсания он получает дополнительную удовлетворённость \
-                         жизнью от количества сисяндр в последних {N} локациях: {} (итого {}), умноженного \
-                         на {MULTIPLIER:.2}: {:.2}.",
-                        state.player.name,
-                        state.player.nationality.name_singular,
-                        loc_iter
-                            .map(|loc| format!("{} - {}", loc.name, loc.fauna[Sisyandra]))
+                    narrate!(
+                        state,
+                        "prosvetlyonnye.shlamsing",
+                        name = state.player.name,
+                        nation = state.player.nationality.name_singular(),
+                        n = N,
+                        locations = loc_iter
+                            .map(|loc| format!("{} - {}", loc.name(), loc.fauna[SISYANDRA]))
                             .collect::<Vec<_>>()
                             .join(", "),
-                        sis_count,
-                        satisfaction_bonus
+                        count = sis_count,
+                        multiplier = multiplier,
+                        bonus = satisfaction_bonus
                     );
 
-                    state.resource_change[Satisfaction] += satisfaction_bonus;
+                    state.resource_change[SATISFACTION] += satisfaction_bonus;
                 }
+                Ok(())
             }),
         },
         Nation {
+            id: DROCENTY,
             name_plural: "Дроценты",
             name_singular: "Дроцент",
-            race: Scoofick,
+            race: SCOOFICK,
             pre_effect: None,
             post_effect: Some(&|state, action| {
-                // Практически не умеют гульбонить, затрачивая вполовину меньше здоровья и денег,
-                // и получая вполовину меньше удовлетворенности.
-                if action.occupation == Some(Goolboning) {
-                    println!(
-                        "{} - {}, и потому гульбонит вполсилы.",
-                        state.player.name,
-                        state.player.nationality.name_singular,
+                // Практически не умеют гульбонить: здоровье, деньги и удовлетворенность
+                // от гульбонства умножаются на `DrocentyGoolboningFactor` (по условию вполовину).
+                if action.occupation == Some(GOOLBONING) {
+                    narrate!(
+                        state,
+                        "drocenty.goolboning",
+                        name = state.player.name,
+                        nation = state.player.nationality.name_singular(),
                     );
 
-                    state.resource_change[Health] *= 0.5;
-                    state.resource_change[Money] *= 0.5;
-                    state.resource_change[Satisfaction] *= 0.5;
+                    let factor = state.params[DrocentyGoolboningFactor];
+                    state.resource_change[HEALTH] *= factor;
+                    state.resource_change[MONEY] *= factor;
+                    state.resource_change[SATISFACTION] *= factor;
                 }
+                Ok(())
             }),
         },
         Nation {
+            id: NationId("zheleznoukhie"),
             name_plural: "Железноухие",
             name_singular: "Железноухий",
-            race: Scoofick,
+            race: SCOOFICK,
             pre_effect: None,
             post_effect: Some(&|state, action| {
-                // Не расходуют удовлетворенность жизнью при зумбальстве, зато с вероятностью 0.33
-                // не получают денег от каждой слесандры в локации.
-                if action.occupation == Some(Zoombaling) {
-                    println!(
-                        "{} - {}, и потому не расходует здоровье при зумбальстве.",
-                        state.player.name,
-                        state.player.nationality.name_singular,
+                // Не расходуют удовлетворенность жизнью при зумбальстве, зато с вероятностью
+                // `ZheleznoukhieMissChance` (по условию 0.33) не получают денег от каждой
+                // слесандры в локации.
+                if action.occupation == Some(ZOOMBALING) {
+                    narrate!(
+                        state,
+                        "zheleznoukhie.zoombaling",
+                        name = state.player.name,
+                        nation = state.player.nationality.name_singular(),
                     );
 
-                    state.resource_change[Health] = 0.0;
+                    state.resource_change[HEALTH] = 0.0;
 
-                    let sles_count = state.effective_fauna[Slesandra];
+                    // Теряется ровно то, что слесандра принесла бы.
+                    let lost = state.yield_per_head(ZOOMBALING.try_get()?, SLESANDRA);
+                    let sles_count = state.effective_fauna[SLESANDRA];
                     for i in 0..sles_count {
-                        if state.chance(0.33) {
-                            println!("Слесандра №{} оставила нашего железноухого без денег!", i + 1);
-                            state.resource_change[Money] -= 2.0;
+                        if state.chance(state.params[ZheleznoukhieMissChance]) {
+                            narrate!(state, "zheleznoukhie.miss", name = state.player.name, n = i + 1);
+                            state.resource_change[MONEY] -= lost;
                         }
                     }
                 }
+                Ok(())
             }),
         },
-    ];
+    ]
+}
+
+pub fn random_nation(rng: &mut impl rand::Rng) -> Result<&'static Nation, SimError> {
+    NATIONS.choose(rng).ok_or_else(|| missing(ContentKind::Nation))
+}
+
+impl Race {
+    /// Название расы на текущем языке.
+    pub fn name(&self) -> &'static str {
+        text_or(&format!("race.{}.name", self.id), self.name)
+    }
+}
+
+impl Nation {
+    /// Название нации во множественном числе на текущем языке.
+    pub fn name_plural(&self) -> &'static str {
+        text_or(&format!("nation.{}.plural", self.id), self.name_plural)
+    }
+
+    /// Название нации в единственном числе на текущем языке.
+    pub fn name_singular(&self) -> &'static str {
+        text_or(&format!("nation.{}.singular", self.id), self.name_singular)
+    }
+
+    /// Порядковый номер нации в `NATIONS`. Ошибка - если нации нет
+    /// в установленном содержимом игры.
+    pub fn index(&self) -> Result<usize, SimError> {
+        NATIONS
+            .iter()
+            .position(|nation| nation.id == self.id)
+            .ok_or_else(|| {
+                SimError::new(SimErrorKind::UnknownId {
+                    kind: ContentKind::Nation,
+                    id: self.id.to_string(),
+                })
+            })
+    }
+}
+
+impl NationId {
+    pub fn try_get(self) -> Result<&'static Nation, SimError> {
+        NATIONS
+            .iter()
+            .find(|nation| nation.id == self)
+            .ok_or_else(|| {
+                SimError::new(SimErrorKind::UnknownId {
+                    kind: ContentKind::Nation,
+                    id: self.to_string(),
+                })
+            })
+    }
+
+    /// Название нации во множественном числе на текущем языке, а если
+    /// такой нации нет в содержимом игры - её идентификатор.
+    pub fn name_plural(self) -> &'static str {
+        self.try_get().map_or(self.0, Nation::name_plural)
+    }
+
+    /// Название нации в единственном числе на текущем языке, а если
+    /// такой нации нет в содержимом игры - её идентификатор.
+    pub fn name_singular(self) -> &'static str {
+        self.try_get().map_or(self.0, Nation::name_singular)
+    }
+
+    /// Идентификатор нации с ключом `key`, если такая нация есть.
+    pub fn parse(key: &str) -> Option<Self> {
+        NATIONS.iter().find(|nation| nation.id.0 == key).map(|nation| nation.id)
+    }
+}
+
+impl RaceId {
+    pub fn try_get(self) -> Result<&'static Race, SimError> {
+        RACES.iter().find(|race| race.id == self).ok_or_else(|| {
+            SimError::new(SimErrorKind::UnknownId {
+                kind: ContentKind::Race,
+                id: self.to_string(),
+            })
+        })
+    }
+
+    /// Название расы на текущем языке, а если такой расы нет
+    /// в содержимом игры - её идентификатор.
+    pub fn name(self) -> &'static str {
+        self.try_get().map_or(self.0, Race::name)
+    }
+
+    /// Идентификатор расы с ключом `key`, если такая раса есть.
+    pub fn parse(key: &str) -> Option<Self> {
+        RACES.iter().find(|race| race.id.0 == key).map(|race| race.id)
+    }
+}
+
+impl PartialEq for Nation {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Nation {}
+
+impl hash::Hash for Nation {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
 }
 
-pub fn random_nation(rng: &mut impl rand::Rng) -> &'static Nation {
-    NATIONS.choose(rng).expect("NATIONS Vec can't be empty!")
+impl fmt::Display for Race {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
 }
 
 pub fn find_nation(name: &str) -> Option<&'static Nation> {
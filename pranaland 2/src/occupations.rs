@@ -0,0 +1,131 @@
+use crate::{
+    definitions::{GameState, ResourceMap, SimError, SimErrorKind},
+    fauna::{CHUCHUNDRA, SISYANDRA, SLESANDRA},
+    i18n::text_or,
+    ids::{FaunaId, OccupationId, ResourceId},
+    locations::Location,
+    registry::{content, ContentKind},
+    resources::{amounts, HEALTH, MONEY, SATISFACTION},
+};
+use lazy_static::lazy_static;
+
+/// Зумбалить - обменивать здоровье и радость на деньги.
+pub const ZOOMBALING: OccupationId = OccupationId("zoombaling");
+
+/// Гульбонить - обменивать деньги и здоровье на удовольствия.
+pub const GOOLBONING: OccupationId = OccupationId("goolboning");
+
+/// Шлямсать - отдавать деньги и лишать себя удовольствий ради здоровья.
+pub const SHLAMSING: OccupationId = OccupationId("shlamsing");
+
+/// Условие, без которого Игрок не может предаться занятию.
+pub struct Prerequisite {
+    /// Описание условия на языке по умолчанию, например "только в Кринжленде".
+    /// Перевод - в каталоге сообщений по ключу `occupation.<id>.requirement`.
+    pub description: &'static str,
+
+    /// Выполнено ли условие для Игрока, который собирается предаться
+    /// занятию в локации (уже с учётом перехода, если он есть).
+    pub check: &'static (dyn Fn(&GameState, &'static Location) -> bool + Sync),
+}
+
+/// Занятие, которому Игрок может предаваться в конце каждого временного
+/// интервала, и его правила.
+pub struct Occupation {
+    pub id: OccupationId,
+
+    /// Название на языке по умолчанию ("Зумбалить").
+    /// Перевод - в каталоге сообщений по ключу `occupation.<id>.name`.
+    pub name: &'static str,
+
+    /// Сколько каждого ресурса расходует занятие, в долях `Param::OccupationCost`.
+    pub consumes: ResourceMap,
+
+    /// Ресурс, который приносит занятие.
+    pub produces: ResourceId,
+
+    /// Фауна, от которой зависит доход, и доход от каждого её представителя
+    /// в долях `Param::FaunaYield`.
+    pub fauna: Vec<(FaunaId, f64)>,
+
+    /// Условие, без которого занятие недоступно, либо `None`, если
+    /// предаваться занятию можно всегда.
+    pub prerequisite: Option<Prerequisite>,
+}
+
+lazy_static! {
+    /// Все занятия из установленного содержимого игры (`content()`).
+    pub static ref OCCUPATIONS: &'static [Occupation] =
+        content().map(|content| content.occupations.as_slice()).unwrap_or_default();
+}
+
+/// Занятия базового набора содержимого.
+pub fn base_occupations() -> Vec<Occupation> {
+    vec![
+        Occupation {
+            id: ZOOMBALING,
+            name: "Зумбалить",
+            consumes: amounts(&[(HEALTH, 1.0), (SATISFACTION, 1.0)]),
+            produces: MONEY,
+            fauna: vec![(SLESANDRA, 1.0)],
+            prerequisite: None,
+        },
+        Occupation {
+            id: GOOLBONING,
+            name: "Гульбонить",
+            consumes: amounts(&[(HEALTH, 1.0), (MONEY, 1.0)]),
+            produces: SATISFACTION,
+            fauna: vec![(SISYANDRA, 1.0)],
+            prerequisite: None,
+        },
+        Occupation {
+            id: SHLAMSING,
+            name: "Шлямсить",
+            consumes: amounts(&[(MONEY, 1.0), (SATISFACTION, 1.0)]),
+            produces: HEALTH,
+            fauna: vec![(CHUCHUNDRA, 1.0)],
+            prerequisite: None,
+        },
+    ]
+}
+
+impl Occupation {
+    /// Название занятия на текущем языке.
+    pub fn name(&self) -> &'static str {
+        text_or(&format!("occupation.{}.name", self.id), self.name)
+    }
+
+    /// Первое занятие, восполняющее данный ресурс, либо `None`, если
+    /// такого занятия нет.
+    pub fn producing(resource: ResourceId) -> Option<OccupationId> {
+        OCCUPATIONS
+            .iter()
+            .find(|occupation| occupation.produces == resource)
+            .map(|occupation| occupation.id)
+    }
+}
+
+impl OccupationId {
+    pub fn try_get(self) -> Result<&'static Occupation, SimError> {
+        OCCUPATIONS
+            .iter()
+            .find(|occupation| occupation.id == self)
+            .ok_or_else(|| {
+                SimError::new(SimErrorKind::UnknownId {
+                    kind: ContentKind::Occupation,
+                    id: self.to_string(),
+                })
+            })
+    }
+
+    /// Название занятия на текущем языке, а если такого занятия нет
+    /// в содержимом игры - его идентификатор.
+    pub fn name(self) -> &'static str {
+        self.try_get().map_or(self.0, Occupation::name)
+    }
+
+    /// Описание условия `description` занятия на текущем языке.
+    pub fn requirement(self, description: &'static str) -> &'static str {
+        text_or(&format!("occupation.{self}.requirement"), description)
+    }
+}
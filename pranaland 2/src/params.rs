@@ -0,0 +1,182 @@
+use crate::{
+    i18n::{localized_display, text_or},
+    ids::{IdMap, ParamId},
+    registry::content,
+};
+use enum_map::EnumMap;
+
+/// Числовые параметры правил базового набора содержимого: стоимости и
+/// доходы занятий, множители и вероятности эффектов наций и локаций.
+///
+/// Эффекты берут числа из таблицы параметров (`GameState::params`), а не
+/// из литералов, поэтому значение каждого параметра можно менять, проверяя,
+/// как оно влияет на выживаемость (см. `sensitivity`). Параметры эффектов
+/// из модов объявляются в самих модах (`ParamDef`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, enum_map::Enum, strum::EnumIter)]
+pub enum Param {
+    /// Сколько единиц каждого ресурса стоит занятие.
+    OccupationCost,
+
+    /// Сколько единиц ресурса приносит каждый представитель фауны.
+    FaunaYield,
+
+    /// Сколько единиц каждого ресурса стоит безделье.
+    IdleCost,
+
+    /// Можоры: множитель трат денег при гульбонстве.
+    MozhoryGoolboningMoney,
+
+    /// Можоры: вероятность не потратить здоровье при зумбальстве.
+    MozhoryShirkChance,
+
+    /// Нищебороды: множитель трат денег при гульбонстве.
+    NischeborodyGoolboningMoney,
+
+    /// Нищебороды: множитель трат здоровья при гульбонстве.
+    NischeborodyGoolboningHealth,
+
+    /// Соевые: сколько здоровья стоит каждая чучундра в локации при зумбальстве.
+    SoyChuchundraPenalty,
+
+    /// Просветлённые: сколько удовлетворённости при шлямсании приносит
+    /// каждая сисяндра в последних локациях.
+    ProsvetlyonnyeSisyandraBonus,
+
+    /// Дроценты: множитель здоровья, денег и удовлетворённости от гульбонства.
+    DrocentyGoolboningFactor,
+
+    /// Железноухие: вероятность не получить денег от слесандры при зумбальстве.
+    ZheleznoukhieMissChance,
+
+    /// Балбесбург: вероятность, что слесандра нанесёт урон здоровью.
+    BalbesburgDamageChance,
+
+    /// Балбесбург: урон здоровью от одной слесандры.
+    BalbesburgDamage,
+
+    /// Долбесбург: множитель денег от зумбальства.
+    DolbesburgMoney,
+
+    /// Долбесбург: множитель трат удовлетворённости при зумбальстве.
+    DolbesburgSatisfaction,
+
+    /// Курамарибы: вероятность, что сисяндра перестанет работать.
+    KuramaribyStrikeChance,
+
+    /// Пунта-пеликана: множитель удовлетворённости от гульбонства.
+    PuntaPelikanaSatisfaction,
+
+    /// Пунта-пеликана: вероятность проиграться в казино.
+    PuntaPelikanaCasinoChance,
+
+    /// Пунта-пеликана: доля денег, проигрываемая в казино.
+    PuntaPelikanaCasinoLoss,
+
+    /// Шринавас: множитель здоровья от шлямсания.
+    ShrinavasHealth,
+
+    /// Харе-Кириши: доля здоровья, которую Дроценты теряют за интервал.
+    HareKirishiDamage,
+}
+
+// Названия параметров - в каталогах сообщений (`i18n`).
+localized_display!(Param => "param");
+
+/// Значение каждого параметра.
+pub type ParamMap = EnumMap<Param, f64>;
+
+impl Param {
+    /// Значение параметра из условия задачи.
+    pub fn default_value(self) -> f64 {
+        use Param::*;
+        match self {
+            OccupationCost => 1.0,
+            FaunaYield => 2.0,
+            IdleCost => 0.5,
+            MozhoryGoolboningMoney => 1.23,
+            MozhoryShirkChance => 1.0 / 3.0,
+            NischeborodyGoolboningMoney => 1.0 - 0.87,
+            NischeborodyGoolboningHealth => 1.76,
+            SoyChuchundraPenalty => 0.12,
+            ProsvetlyonnyeSisyandraBonus => 0.31,
+            DrocentyGoolboningFactor => 0.5,
+            ZheleznoukhieMissChance => 0.33,
+            BalbesburgDamageChance => 0.15,
+            BalbesburgDamage => 0.1,
+            DolbesburgMoney => 1.2,
+            DolbesburgSatisfaction => 1.3,
+            KuramaribyStrikeChance => 0.7,
+            PuntaPelikanaSatisfaction => 1.23,
+            PuntaPelikanaCasinoChance => 0.2,
+            PuntaPelikanaCasinoLoss => 0.5,
+            ShrinavasHealth => 1.13,
+            HareKirishiDamage => 0.1,
+        }
+    }
+
+    /// Является ли параметр вероятностью (и, значит, должен лежать в `0.0..=1.0`).
+    pub fn is_probability(self) -> bool {
+        use Param::*;
+        matches!(
+            self,
+            MozhoryShirkChance
+                | ZheleznoukhieMissChance
+                | BalbesburgDamageChance
+                | KuramaribyStrikeChance
+                | PuntaPelikanaCasinoChance
+        )
+    }
+
+    /// Допустимо ли значение параметра: все параметры неотрицательны
+    /// (иначе, например, расходы превратились бы в доходы),
+    /// а вероятности не больше единицы.
+    pub fn accepts(self, value: f64) -> bool {
+        value.is_finite() && value >= 0.0 && (!self.is_probability() || value <= 1.0)
+    }
+}
+
+/// Таблица параметров из условия задачи.
+pub fn default_params() -> ParamMap {
+    EnumMap::from_fn(Param::default_value)
+}
+
+/// Параметр правил, объявленный набором содержимого - например, модом
+/// для своих эффектов. Эффекты узнают его значение через
+/// `GameState::param`, а описания локаций подставляют его по
+/// идентификатору, как и параметры `Param`.
+pub struct ParamDef {
+    pub id: ParamId,
+
+    /// Человекочитаемое название параметра.
+    /// Перевод - в каталоге сообщений по ключу `param.<id>`.
+    pub name: &'static str,
+
+    /// Значение параметра, если его не изменили.
+    pub default: f64,
+
+    /// Является ли параметр вероятностью (см. `Param::is_probability`).
+    pub probability: bool,
+}
+
+/// Значения параметров, объявленных содержимым игры.
+pub type ContentParamMap = IdMap<ParamId, f64>;
+
+impl ParamDef {
+    /// Название параметра на текущем языке.
+    pub fn name(&self) -> &'static str {
+        text_or(&format!("param.{}", self.id), self.name)
+    }
+
+    /// Допустимо ли значение параметра - по тем же правилам, что и `Param::accepts`.
+    pub fn accepts(&self, value: f64) -> bool {
+        value.is_finite() && value >= 0.0 && (!self.probability || value <= 1.0)
+    }
+}
+
+/// Параметры, объявленные установленным содержимым игры, со значениями
+/// по умолчанию. Если содержимое не загрузилось, таблица пуста.
+pub fn default_content_params() -> ContentParamMap {
+    content()
+        .map(|content| content.params.iter().map(|param| (param.id, param.default)).collect())
+        .unwrap_or_default()
+}
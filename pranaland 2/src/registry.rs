@@ -0,0 +1,726 @@
+use crate::{
+    calendar::{base_events, GlobalEvent},
+    definitions::{ResourceMap, SimError, SimErrorKind},
+    fauna::{base_fauna, Fauna, FaunaBehaviour},
+    i18n::{localized_display, parse_catalog, tr, Catalog, Locale},
+    ids::{FaunaId, ResourceId},
+    locations::{base_biomes, base_locations, Biome, Location},
+    nations::{base_nations, base_races, Nation, Race},
+    occupations::{base_occupations, Occupation},
+    params::{Param, ParamDef},
+    resources::{base_resources, Resource},
+    travel::{base_routes, RouteDef},
+};
+use enum_map::EnumMap;
+use std::{fmt, sync::OnceLock};
+
+/// Набор содержимого игры: базовый или мод.
+///
+/// Мод может как добавлять новое содержимое, так и заменять существующее -
+/// всё, что имеет тот же идентификатор, что и загруженное ранее, заменяется.
+/// Маршрут заменяется маршрутом между теми же локациями.
+pub struct ContentPack {
+    /// Человекочитаемое название набора.
+    pub name: &'static str,
+
+    pub resources: Vec<Resource>,
+    pub occupations: Vec<Occupation>,
+    pub races: Vec<Race>,
+    pub nations: Vec<Nation>,
+    pub biomes: Vec<Biome>,
+    pub locations: Vec<Location>,
+
+    /// Маршруты между биомами. Маршруты внутри биома создаются автоматически.
+    pub routes: Vec<RouteDef>,
+
+    pub fauna: Vec<Fauna>,
+
+    /// Глобальные события. Происходят, только если включён календарь
+    /// (`WorldState::calendar`).
+    pub events: Vec<GlobalEvent>,
+
+    /// Параметры правил для эффектов набора (в дополнение к `Param`).
+    pub params: Vec<ParamDef>,
+
+    /// Каталоги сообщений набора по языкам в формате `locales/<язык>.txt`:
+    /// переводы названий его содержимого и тексты его эффектов. Сообщения
+    /// заменяют сообщения с теми же ключами из каталогов игры и ранее
+    /// загруженных наборов.
+    pub messages: Vec<(Locale, &'static str)>,
+}
+
+/// Всё содержимое игры после загрузки базового набора и модов.
+pub struct ContentRegistry {
+    /// Названия загруженных наборов в порядке загрузки.
+    pub packs: Vec<&'static str>,
+
+    pub resources: Vec<Resource>,
+    pub occupations: Vec<Occupation>,
+    pub races: Vec<Race>,
+    pub nations: Vec<Nation>,
+    pub biomes: Vec<Biome>,
+    pub locations: Vec<Location>,
+    pub routes: Vec<RouteDef>,
+    pub fauna: Vec<Fauna>,
+    pub events: Vec<GlobalEvent>,
+    pub params: Vec<ParamDef>,
+
+    /// Сообщения всех наборов по языкам (см. `i18n::lookup`).
+    pub messages: EnumMap<Locale, Catalog>,
+}
+
+/// Вид объектов содержимого игры. Название вида на текущем языке -
+/// в каталоге сообщений по ключу `content_kind.<Вариант>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentKind {
+    Resource,
+    Occupation,
+    Race,
+    Nation,
+    Biome,
+    Location,
+    Route,
+    Fauna,
+    Event,
+    Param,
+}
+
+localized_display!(ContentKind => "content_kind");
+
+/// Ошибка в содержимом игры.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContentError {
+    /// Идентификатор встречается в одном наборе несколько раз.
+    DuplicateId {
+        pack: &'static str,
+        kind: ContentKind,
+        id: String,
+    },
+
+    /// Содержимое ссылается на то, чего нет.
+    DanglingReference {
+        kind: ContentKind,
+        id: String,
+        target_kind: ContentKind,
+        target: String,
+    },
+
+    /// После загрузки всех наборов не осталось ни одного элемента нужного вида.
+    Empty { kind: ContentKind },
+
+    /// Вероятность или доля вне `0.0..=1.0`.
+    InvalidProbability {
+        kind: ContentKind,
+        id: String,
+        field: &'static str,
+        value: f64,
+    },
+
+    /// Параметр правил, который должен быть вероятностью, ею не является
+    /// (см. `Param::is_probability`).
+    NotAProbability {
+        kind: ContentKind,
+        id: String,
+        field: &'static str,
+        param: Param,
+    },
+
+    /// Отрицательное или неопределённое значение там, где оно должно быть
+    /// неотрицательным (например, стоимость дороги, ставшая доходом).
+    NegativeValue {
+        kind: ContentKind,
+        id: String,
+        field: &'static str,
+        value: f64,
+    },
+
+    /// Строка каталога сообщений набора не имеет вида `ключ = шаблон`.
+    MalformedMessage { pack: &'static str, line: String },
+}
+
+/// Все ошибки, найденные при загрузке содержимого.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentErrors(pub Vec<ContentError>);
+
+/// Добавляет элементы `items` из набора `pack` в `into`, заменяя элементы
+/// с тем же идентификатором. О повторах идентификатора внутри набора
+/// сообщается в `errors`.
+fn merge<T>(
+    pack: &'static str,
+    kind: ContentKind,
+    into: &mut Vec<T>,
+    items: Vec<T>,
+    id: impl Fn(&T) -> String,
+    errors: &mut Vec<ContentError>,
+) {
+    let mut seen: Vec<String> = Vec::new();
+    for item in items {
+        let key = id(&item);
+        if seen.contains(&key) {
+            errors.push(ContentError::DuplicateId {
+                pack,
+                kind,
+                id: key.clone(),
+            });
+        }
+
+        match into.iter().position(|existing| id(existing) == key) {
+            Some(i) => into[i] = item,
+            None => into.push(item),
+        }
+        seen.push(key);
+    }
+}
+
+fn route_key(route: &RouteDef) -> String {
+    format!("{} -> {}", route.from, route.to)
+}
+
+/// Сообщает в `errors`, если `resource` нет среди ресурсов `resources`.
+fn check_resource(
+    resources: &[Resource],
+    kind: ContentKind,
+    id: String,
+    resource: ResourceId,
+    errors: &mut Vec<ContentError>,
+) {
+    if !resources.iter().any(|res| res.id == resource) {
+        errors.push(ContentError::DanglingReference {
+            kind,
+            id,
+            target_kind: ContentKind::Resource,
+            target: resource.to_string(),
+        });
+    }
+}
+
+/// Сообщает в `errors`, если `species` нет среди видов фауны `fauna`.
+fn check_fauna(
+    fauna: &[Fauna],
+    kind: ContentKind,
+    id: String,
+    species: FaunaId,
+    errors: &mut Vec<ContentError>,
+) {
+    if !fauna.iter().any(|f| f.id == species) {
+        errors.push(ContentError::DanglingReference {
+            kind,
+            id,
+            target_kind: ContentKind::Fauna,
+            target: species.to_string(),
+        });
+    }
+}
+
+/// Сообщает в `errors`, если значение поля `field` - не вероятность
+/// (или доля) из `0.0..=1.0`.
+fn check_probability(
+    kind: ContentKind,
+    id: String,
+    field: &'static str,
+    value: f64,
+    errors: &mut Vec<ContentError>,
+) {
+    if !(0.0..=1.0).contains(&value) {
+        errors.push(ContentError::InvalidProbability {
+            kind,
+            id,
+            field,
+            value,
+        });
+    }
+}
+
+/// Сообщает в `errors`, если значение поля `field` отрицательно
+/// или не определено.
+fn check_non_negative(
+    kind: ContentKind,
+    id: String,
+    field: &'static str,
+    value: f64,
+    errors: &mut Vec<ContentError>,
+) {
+    if value.is_nan() || value < 0.0 {
+        errors.push(ContentError::NegativeValue {
+            kind,
+            id,
+            field,
+            value,
+        });
+    }
+}
+
+/// Проверяет ресурсы, которые тратит `kind` `id`, и что траты
+/// неотрицательны.
+fn check_costs(
+    resources: &[Resource],
+    kind: ContentKind,
+    id: String,
+    field: &'static str,
+    costs: &ResourceMap,
+    errors: &mut Vec<ContentError>,
+) {
+    for (resource, &amount) in costs {
+        check_resource(resources, kind, id.clone(), resource, errors);
+        check_non_negative(kind, format!("{id} [{resource}]"), field, amount, errors);
+    }
+}
+
+/// Проверяет ресурс, которому вредит поведение фауны, и что вероятность
+/// вреда - действительно вероятность.
+fn check_behaviour(
+    resources: &[Resource],
+    kind: ContentKind,
+    id: String,
+    behaviour: &FaunaBehaviour,
+    errors: &mut Vec<ContentError>,
+) {
+    match *behaviour {
+        FaunaBehaviour::Aggressive {
+            resource, chance, ..
+        } => {
+            if !chance.is_probability() {
+                errors.push(ContentError::NotAProbability {
+                    kind,
+                    id: id.clone(),
+                    field: "chance",
+                    param: chance,
+                });
+            }
+            check_resource(resources, kind, id, resource, errors);
+        }
+    }
+}
+
+impl ContentRegistry {
+    /// Загружает базовый набор, а поверх него - моды в указанном порядке.
+    /// Если содержимое некорректно, возвращает все найденные ошибки
+    /// (`SimErrorKind::Content`).
+    pub fn load(base: ContentPack, mods: Vec<ContentPack>) -> Result<Self, SimError> {
+        let mut errors = Vec::new();
+        let mut packs = Vec::new();
+        let mut resources = Vec::new();
+        let mut occupations = Vec::new();
+        let mut races = Vec::new();
+        let mut nations = Vec::new();
+        let mut biomes = Vec::new();
+        let mut locations = Vec::new();
+        let mut routes = Vec::new();
+        let mut fauna = Vec::new();
+        let mut events = Vec::new();
+        let mut params = Vec::new();
+        let mut messages: EnumMap<Locale, Catalog> = EnumMap::default();
+
+        for pack in std::iter::once(base).chain(mods) {
+            let name = pack.name;
+            packs.push(name);
+
+            for (locale, source) in pack.messages {
+                match parse_catalog(source) {
+                    Ok(catalog) => messages[locale].extend(catalog),
+                    Err(line) => errors.push(ContentError::MalformedMessage {
+                        pack: name,
+                        line: line.to_string(),
+                    }),
+                }
+            }
+
+            merge(
+                name,
+                ContentKind::Resource,
+                &mut resources,
+                pack.resources,
+                |r| r.id.to_string(),
+                &mut errors,
+            );
+            merge(
+                name,
+                ContentKind::Occupation,
+                &mut occupations,
+                pack.occupations,
+                |o| o.id.to_string(),
+                &mut errors,
+            );
+            merge(
+                name,
+                ContentKind::Race,
+                &mut races,
+                pack.races,
+                |r| r.id.to_string(),
+                &mut errors,
+            );
+            merge(
+                name,
+                ContentKind::Nation,
+                &mut nations,
+                pack.nations,
+                |n| n.id.to_string(),
+                &mut errors,
+            );
+            merge(
+                name,
+                ContentKind::Biome,
+                &mut biomes,
+                pack.biomes,
+                |b| b.id.to_string(),
+                &mut errors,
+            );
+            merge(
+                name,
+                ContentKind::Location,
+                &mut locations,
+                pack.locations,
+                |l| l.id.to_string(),
+                &mut errors,
+            );
+            merge(
+                name,
+                ContentKind::Route,
+                &mut routes,
+                pack.routes,
+                route_key,
+                &mut errors,
+            );
+            merge(
+                name,
+                ContentKind::Fauna,
+                &mut fauna,
+                pack.fauna,
+                |f| f.id.to_string(),
+                &mut errors,
+            );
+            merge(
+                name,
+                ContentKind::Event,
+                &mut events,
+                pack.events,
+                |e| e.id.to_string(),
+                &mut errors,
+            );
+            merge(
+                name,
+                ContentKind::Param,
+                &mut params,
+                pack.params,
+                |p| p.id.to_string(),
+                &mut errors,
+            );
+        }
+
+        for (kind, len) in [
+            (ContentKind::Resource, resources.len()),
+            (ContentKind::Race, races.len()),
+            (ContentKind::Nation, nations.len()),
+            (ContentKind::Biome, biomes.len()),
+            (ContentKind::Location, locations.len()),
+        ] {
+            if len == 0 {
+                errors.push(ContentError::Empty { kind });
+            }
+        }
+
+        for resource in &resources {
+            let id = resource.id.to_string();
+            check_probability(ContentKind::Resource, id.clone(), "decay", resource.decay, &mut errors);
+            check_non_negative(ContentKind::Resource, id, "idle_cost", resource.idle_cost, &mut errors);
+        }
+
+        for occupation in &occupations {
+            let id = occupation.id.to_string();
+            check_costs(
+                &resources,
+                ContentKind::Occupation,
+                id.clone(),
+                "consumes",
+                &occupation.consumes,
+                &mut errors,
+            );
+            check_resource(
+                &resources,
+                ContentKind::Occupation,
+                id.clone(),
+                occupation.produces,
+                &mut errors,
+            );
+            for &(species, factor) in &occupation.fauna {
+                check_fauna(&fauna, ContentKind::Occupation, id.clone(), species, &mut errors);
+                check_non_negative(
+                    ContentKind::Occupation,
+                    format!("{id} [{species}]"),
+                    "fauna",
+                    factor,
+                    &mut errors,
+                );
+            }
+        }
+
+        for biome in &biomes {
+            for (season, &productivity) in &biome.productivity {
+                check_non_negative(
+                    ContentKind::Biome,
+                    format!("{} [{season:?}]", biome.id),
+                    "productivity",
+                    productivity,
+                    &mut errors,
+                );
+            }
+        }
+
+        for nation in &nations {
+            if !races.iter().any(|race| race.id == nation.race) {
+                errors.push(ContentError::DanglingReference {
+                    kind: ContentKind::Nation,
+                    id: nation.id.to_string(),
+                    target_kind: ContentKind::Race,
+                    target: nation.race.to_string(),
+                });
+            }
+        }
+
+        for location in &locations {
+            if !biomes.iter().any(|biome| biome.id == location.biome) {
+                errors.push(ContentError::DanglingReference {
+                    kind: ContentKind::Location,
+                    id: location.id.to_string(),
+                    target_kind: ContentKind::Biome,
+                    target: location.biome.to_string(),
+                });
+            }
+            for (species, _) in &location.fauna {
+                check_fauna(
+                    &fauna,
+                    ContentKind::Location,
+                    location.id.to_string(),
+                    species,
+                    &mut errors,
+                );
+            }
+            for &(species, ref behaviour) in &location.fauna_behaviours {
+                check_fauna(
+                    &fauna,
+                    ContentKind::Location,
+                    location.id.to_string(),
+                    species,
+                    &mut errors,
+                );
+                check_behaviour(
+                    &resources,
+                    ContentKind::Location,
+                    location.id.to_string(),
+                    behaviour,
+                    &mut errors,
+                );
+            }
+            if let Some(dynamics) = location.dynamics {
+                for (field, value) in dynamics.invalid_fields() {
+                    errors.push(ContentError::InvalidProbability {
+                        kind: ContentKind::Location,
+                        id: location.id.to_string(),
+                        field,
+                        value,
+                    });
+                }
+            }
+        }
+
+        for route in &routes {
+            for end in [route.from, route.to] {
+                if !locations.iter().any(|location| location.id == end) {
+                    errors.push(ContentError::DanglingReference {
+                        kind: ContentKind::Route,
+                        id: route_key(route),
+                        target_kind: ContentKind::Location,
+                        target: end.to_string(),
+                    });
+                }
+            }
+            check_costs(
+                &resources,
+                ContentKind::Route,
+                route_key(route),
+                "cost",
+                &route.cost,
+                &mut errors,
+            );
+        }
+
+        for species in &fauna {
+            for behaviour in &species.behaviours {
+                check_behaviour(
+                    &resources,
+                    ContentKind::Fauna,
+                    species.id.to_string(),
+                    behaviour,
+                    &mut errors,
+                );
+            }
+        }
+
+        for event in &events {
+            let id = event.id.to_string();
+            check_probability(ContentKind::Event, id.clone(), "chance", event.chance, &mut errors);
+            check_costs(&resources, ContentKind::Event, id.clone(), "income", &event.income, &mut errors);
+            check_costs(&resources, ContentKind::Event, id, "upkeep", &event.upkeep, &mut errors);
+        }
+
+        for param in &params {
+            let id = param.id.to_string();
+            if param.probability {
+                check_probability(ContentKind::Param, id, "default", param.default, &mut errors);
+            } else {
+                check_non_negative(ContentKind::Param, id, "default", param.default, &mut errors);
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(ContentErrors(errors).into());
+        }
+
+        Ok(Self {
+            packs,
+            resources,
+            occupations,
+            races,
+            nations,
+            biomes,
+            locations,
+            routes,
+            fauna,
+            events,
+            params,
+            messages,
+        })
+    }
+}
+
+/// Базовый набор содержимого - Дурляндия из условия задачи.
+pub fn base_pack() -> ContentPack {
+    ContentPack {
+        name: "Дурляндия",
+        resources: base_resources(),
+        occupations: base_occupations(),
+        races: base_races(),
+        nations: base_nations(),
+        biomes: base_biomes(),
+        locations: base_locations(),
+        routes: base_routes(),
+        fauna: base_fauna(),
+        events: base_events(),
+        params: Vec::new(),
+        messages: Vec::new(),
+    }
+}
+
+static CONTENT: OnceLock<ContentRegistry> = OnceLock::new();
+
+/// Установленное содержимое игры. Если до первого обращения содержимое
+/// не было установлено функцией `install`, используется базовый набор,
+/// а если и он некорректен - возвращается ошибка его загрузки. Списки
+/// содержимого (`LOCATIONS`, `NATIONS` и т.д.) тогда пусты.
+pub fn content() -> Result<&'static ContentRegistry, SimError> {
+    if let Some(registry) = CONTENT.get() {
+        return Ok(registry);
+    }
+    let registry = ContentRegistry::load(base_pack(), Vec::new())?;
+    Ok(CONTENT.get_or_init(|| registry))
+}
+
+/// Установленное содержимое игры, если к нему уже обращались. В отличие
+/// от `content`, не загружает базовый набор.
+pub(crate) fn installed() -> Option<&'static ContentRegistry> {
+    CONTENT.get()
+}
+
+/// Ошибка, когда в содержимом игры не оказалось ни одного объекта `kind`:
+/// ошибка загрузки содержимого, если оно не загрузилось, иначе
+/// `ContentError::Empty`.
+pub(crate) fn missing(kind: ContentKind) -> SimError {
+    content().err().unwrap_or_else(|| {
+        SimError::new(SimErrorKind::Content(ContentErrors(vec![ContentError::Empty {
+            kind,
+        }])))
+    })
+}
+
+/// Устанавливает содержимое игры. Это возможно только до первого обращения
+/// к содержимому - иначе содержимое возвращается обратно.
+pub fn install(registry: ContentRegistry) -> Result<(), Box<ContentRegistry>> {
+    CONTENT.set(registry).map_err(Box::new)
+}
+
+impl fmt::Display for ContentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ContentError::DuplicateId { pack, kind, id } => tr!(
+                "content_error.duplicate_id",
+                pack = pack,
+                kind = kind,
+                id = id
+            ),
+            ContentError::DanglingReference {
+                kind,
+                id,
+                target_kind,
+                target,
+            } => tr!(
+                "content_error.dangling_reference",
+                kind = kind,
+                id = id,
+                target_kind = target_kind,
+                target = target
+            ),
+            ContentError::Empty { kind } => tr!("content_error.empty", kind = kind),
+            ContentError::InvalidProbability {
+                kind,
+                id,
+                field,
+                value,
+            } => tr!(
+                "content_error.invalid_probability",
+                kind = kind,
+                id = id,
+                field = field,
+                value = value
+            ),
+            ContentError::NotAProbability {
+                kind,
+                id,
+                field,
+                param,
+            } => tr!(
+                "content_error.not_a_probability",
+                kind = kind,
+                id = id,
+                field = field,
+                param = param
+            ),
+            ContentError::NegativeValue {
+                kind,
+                id,
+                field,
+                value,
+            } => tr!(
+                "content_error.negative_value",
+                kind = kind,
+                id = id,
+                field = field,
+                value = value
+            ),
+            ContentError::MalformedMessage { pack, line } => {
+                tr!("content_error.malformed_message", pack = pack, line = line)
+            }
+        };
+        write!(f, "{message}")
+    }
+}
+
+impl fmt::Display for ContentErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for error in &self.0 {
+            writeln!(f, "- {error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ContentErrors {}
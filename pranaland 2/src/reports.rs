@@ -0,0 +1,568 @@
+//! Отчёты об исследовании правил игры, которые строит `pranaland`
+//! из командной строки. Отчёты пишутся в `out`, а куда - решает
+//! вызывающий, например бинарник выводит их в STDOUT.
+
+use crate::{
+    attribution::DeathAttribution,
+    batch::{run_batch, BatchConfig},
+    definitions::{GameState, SimError, Strategy},
+    demography::{write_census_csv, Demography, Reproduction},
+    genetic::{evolve, nation_fitness, GeneticConfig},
+    i18n::{text, tr},
+    information::{analyze_actions, Belief},
+    multiplayer::{run_world, World},
+    nations::{NATIONS, RACES},
+    params::ParamMap,
+    resources::RESOURCES,
+    risk::{Objective, RiskReport},
+    robust::{optimize_robust, RobustObjective},
+    sensitivity::{analyze_sensitivity, DEFAULT_FACTORS},
+    strategies::*,
+    survival::{self, SurvivalCurve},
+};
+use std::{
+    error::Error,
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+/// Подбор оптимальной стратегии на правилах для каждой нации
+/// генетическим алгоритмом.
+pub fn evolve_strategies(out: &mut dyn Write, objective: Objective) -> Result<(), Box<dyn Error>> {
+    let mut config = GeneticConfig::default();
+    config.objective = objective;
+    writeln!(out, "{}", tr!("report.objective", objective = objective))?;
+
+    for nation in NATIONS.iter() {
+        let best = evolve(&config, &|genome| nation_fitness(genome, nation, &config));
+
+        let strategy = RuleStrategy {
+            genome: best.genome.clone(),
+        };
+        let risk = RiskReport::new(
+            &run_batch(&strategy, Some(nation), &config.batch),
+            2.0,
+            config.batch.max_steps,
+            0.1,
+            0.05,
+        );
+
+        header(out, nation.name_plural())?;
+        writeln!(out, "{}", tr!("report.fitness", fitness = best.fitness))?;
+        writeln!(
+            out,
+            "{}",
+            tr!(
+                "report.risk",
+                mean = risk.mean_survival,
+                steps = config.batch.max_steps,
+                ruin = risk.probability_of_ruin,
+                cvar = risk.cvar,
+                certainty = risk.certainty_equivalent
+            )
+        )?;
+        writeln!(out, "{}", best.genome)?;
+    }
+
+    Ok(())
+}
+
+/// Подбор одной стратегии для Игрока, не знающего своей нации,
+/// и отчёт о том, чего ему стоит это незнание.
+pub fn evolve_robust_strategy(
+    out: &mut dyn Write,
+    robust_objective: RobustObjective,
+    objective: Objective,
+) -> Result<(), Box<dyn Error>> {
+    let mut config = GeneticConfig::default();
+    config.objective = objective;
+    let (best, regrets) = optimize_robust(&config, robust_objective)?;
+
+    writeln!(out, "{}", tr!("report.objective", objective = objective))?;
+    writeln!(
+        out,
+        "{}",
+        tr!(
+            "report.robust_fitness",
+            objective = robust_objective,
+            fitness = best.fitness
+        )
+    )?;
+    writeln!(out, "{}", best.genome)?;
+
+    header(out, text("report.regret.title"))?;
+    for regret in &regrets {
+        writeln!(
+            out,
+            "{}",
+            tr!(
+                "report.regret.row",
+                nation = regret.nation.name_plural(),
+                robust = regret.robust,
+                optimum = regret.optimum,
+                regret = regret.regret(),
+                note = if regret.search_failed() {
+                    text("report.regret.search_failed")
+                } else {
+                    ""
+                }
+            )
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Таблица ценности информации для каждого действия в начале игры,
+/// когда Игрок ещё ничего не знает о своей нации.
+pub fn tabulate_information_value(out: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+    const SAMPLES: usize = 200;
+
+    let mut state = GameState::new("Игрок".to_string(), None, 1337)?;
+    state.verbose = false;
+    let belief = Belief::uniform();
+
+    writeln!(
+        out,
+        "{}",
+        tr!(
+            "report.voi.start",
+            location = state.location.name(),
+            entropy = belief.entropy()
+        )
+    )?;
+    writeln!(
+        out,
+        "{}",
+        tr!(
+            "report.voi.row",
+            location = text("report.voi.location"),
+            occupation = text("report.voi.occupation"),
+            information = text("report.voi.information"),
+            cost = text("report.voi.cost")
+        )
+    )?;
+
+    let values = analyze_actions(&state, &belief, SAMPLES)?;
+    for value in values {
+        writeln!(
+            out,
+            "{}",
+            tr!(
+                "report.voi.row",
+                location = value.action.destination.map_or("-", |loc| loc.name()),
+                occupation = value
+                    .action
+                    .occupation
+                    .map_or(text("report.voi.nothing"), |occupation| occupation.name()),
+                information = value.information_gain,
+                cost = value.expected_cost
+            )
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Кривые выживаемости для каждой пары "нация × стратегия" в CSV и SVG.
+pub fn report_survival(out: &mut dyn Write, dir: &Path) -> Result<(), Box<dyn Error>> {
+    let batch = BatchConfig::default();
+    let strategies: [&dyn Strategy; 2] = [&ApatheticStrategy, &RandomStrategy];
+
+    fs::create_dir_all(dir).map_err(|error| tr!("report.create_dir_failed", error = error))?;
+
+    let mut all_curves = Vec::new();
+    for nation in NATIONS.iter() {
+        let curves: Vec<SurvivalCurve> = strategies
+            .iter()
+            .map(|&strategy| {
+                let result = run_batch(strategy, Some(nation), &batch);
+                SurvivalCurve::new(
+                    nation.name_plural(),
+                    strategy.name(),
+                    &result.outcomes,
+                    batch.max_steps,
+                )
+            })
+            .collect();
+
+        header(out, nation.name_plural())?;
+        for curve in &curves {
+            let causes: Vec<String> = RESOURCES
+                .iter()
+                .map(|res| format!("{}: {}", res.name(), curve.death_causes[res.id]))
+                .collect();
+            writeln!(
+                out,
+                "{}",
+                tr!(
+                    "report.survival.row",
+                    strategy = curve.strategy,
+                    survival = curve.final_survival(),
+                    causes = causes.join(", ")
+                )
+            )?;
+        }
+
+        survival::write_svg(
+            &tr!("report.survival.title", nation = nation.name_plural()),
+            &curves,
+            &dir.join(format!("{}.svg", nation.name_plural)),
+        )
+        .map_err(|error| tr!("report.save_svg_failed", error = error))?;
+
+        all_curves.extend(curves);
+    }
+
+    survival::write_csv(&all_curves, &dir.join("survival.csv"))
+        .map_err(|error| tr!("report.save_csv_failed", error = error))?;
+    survival::write_death_causes_csv(&all_curves, &dir.join("death_causes.csv"))
+        .map_err(|error| tr!("report.save_csv_failed", error = error))?;
+    writeln!(out, "{}", tr!("report.saved", path = dir.display()))?;
+
+    Ok(())
+}
+
+/// Какие эффекты приводят к смерти Игроков каждой нации при случайной стратегии.
+pub fn report_death_attribution(out: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+    let batch = BatchConfig::default();
+
+    for nation in NATIONS.iter() {
+        let result = run_batch(&RandomStrategy, Some(nation), &batch);
+        let attribution = DeathAttribution::new(&result.outcomes);
+
+        header(out, nation.name_plural())?;
+        for res in RESOURCES
+            .iter()
+            .filter(|res| attribution.deaths[res.id] > 0)
+        {
+            writeln!(
+                out,
+                "{}",
+                tr!(
+                    "report.attribution.deaths",
+                    resource = res.name(),
+                    n = attribution.deaths[res.id]
+                )
+            )?;
+            for source in attribution.sources() {
+                let share = attribution.loss_share[&source][res.id];
+                let fatal = attribution.fatal.get(&source).map_or(0, |f| f[res.id]);
+                if share > 0.0 || fatal > 0 {
+                    writeln!(
+                        out,
+                        "{}",
+                        tr!(
+                            "report.attribution.source",
+                            source = source,
+                            share = share * 100.0,
+                            fatal = fatal
+                        )
+                    )?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Насколько выживаемость зависит от каждого числового параметра правил.
+/// При `reoptimize == false` стратегии подбираются один раз для правил по
+/// умолчанию, иначе - заново для каждого значения параметра.
+pub fn report_sensitivity(out: &mut dyn Write, reoptimize: bool) -> Result<(), Box<dyn Error>> {
+    let config = GeneticConfig::default();
+    let (robust, _) = optimize_robust(&config, RobustObjective::Mean)?;
+    let rule_strategy = RuleStrategy {
+        genome: robust.genome,
+    };
+
+    let evaluate = |params: &ParamMap| -> Vec<f64> {
+        let batch = BatchConfig {
+            params: *params,
+            ..config.batch
+        };
+        let random = run_batch(&RandomStrategy, None, &batch).mean_survival();
+
+        let rules = if reoptimize {
+            let mut config = config;
+            config.batch = batch;
+            let best = evolve(&config, &|genome| {
+                let strategy = RuleStrategy {
+                    genome: genome.clone(),
+                };
+                run_batch(&strategy, None, &batch).mean_survival()
+            });
+            best.fitness
+        } else {
+            run_batch(&rule_strategy, None, &batch).mean_survival()
+        };
+
+        vec![random, rules]
+    };
+
+    writeln!(
+        out,
+        "{}",
+        tr!(
+            "report.sensitivity.title",
+            random = RandomStrategy.name(),
+            rules = rule_strategy.name(),
+            factors = format!("{DEFAULT_FACTORS:?}")
+        )
+    )?;
+    for sensitivity in analyze_sensitivity(&DEFAULT_FACTORS, &evaluate) {
+        header(
+            out,
+            tr!(
+                "report.sensitivity.param",
+                param = sensitivity.param,
+                swing = sensitivity.swing()
+            ),
+        )?;
+        for point in &sensitivity.points {
+            writeln!(
+                out,
+                "{:>8.3}: {}",
+                point.value,
+                point
+                    .scores
+                    .iter()
+                    .map(|score| format!("{score:>7.2}"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Остаются ли стратегии, оптимальные для одиночки, оптимальными в толпе.
+pub fn report_crowding(out: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+    const WORLDS: u64 = 20;
+
+    let config = GeneticConfig::default();
+    let (robust, _) = optimize_robust(&config, RobustObjective::Mean)?;
+    writeln!(
+        out,
+        "{}",
+        tr!("report.crowding.strategy", genome = robust.genome)
+    )?;
+
+    let rule_strategy = RuleStrategy {
+        genome: robust.genome.clone(),
+    };
+    writeln!(
+        out,
+        "{}",
+        tr!(
+            "report.crowding.row",
+            crowd = text("report.column.inhabitants"),
+            random = RandomStrategy.name(),
+            rules = rule_strategy.name()
+        )
+    )?;
+    for crowd in [1, 2, 5, 10, 20] {
+        let mean_survival = |make: &dyn Fn() -> Box<dyn Strategy>| -> Result<f64, SimError> {
+            let mut total = 0;
+            for seed in 0..WORLDS {
+                let strategies = (0..crowd).map(|_| make()).collect();
+                total += run_world(strategies, seed, config.batch.max_steps)?
+                    .into_iter()
+                    .sum::<usize>();
+            }
+            Ok(total as f64 / (WORLDS as usize * crowd) as f64)
+        };
+
+        let random = mean_survival(&|| Box::new(RandomStrategy))?;
+        let rules = mean_survival(&|| {
+            Box::new(RuleStrategy {
+                genome: robust.genome.clone(),
+            })
+        })?;
+        writeln!(
+            out,
+            "{}",
+            tr!(
+                "report.crowding.row",
+                crowd = crowd,
+                random = random,
+                rules = rules
+            )
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Сравнение выживаемости толпы, которая торгует излишками, и той, что не торгует.
+pub fn report_trading(out: &mut dyn Write) -> Result<(), Box<dyn Error>> {
+    const WORLDS: u64 = 20;
+    const CROWD: usize = 10;
+
+    let config = GeneticConfig::default();
+    let (robust, _) = optimize_robust(&config, RobustObjective::Mean)?;
+    let rules = || -> Box<dyn Strategy> {
+        Box::new(RuleStrategy {
+            genome: robust.genome.clone(),
+        })
+    };
+
+    writeln!(
+        out,
+        "{}",
+        tr!(
+            "report.trading.row",
+            strategy = text("report.column.strategy"),
+            crowd = text("report.column.inhabitants"),
+            survival = text("report.column.mean_survival"),
+            trades = text("report.column.mean_trades")
+        )
+    )?;
+    for trading in [false, true] {
+        let mut survived = 0;
+        let mut trades = 0;
+        let mut name = "";
+        for seed in 0..WORLDS {
+            let mut world = World::new(seed);
+            for i in 0..CROWD {
+                let strategy: Box<dyn Strategy> = if trading {
+                    Box::new(TradingStrategy {
+                        inner: rules(),
+                        reserve: 10.0,
+                        max_price: 1.5,
+                    })
+                } else {
+                    rules()
+                };
+                name = strategy.name();
+                world.add_inhabitant(tr!("world.inhabitant", n = i + 1), None, strategy)?;
+            }
+            while world.alive().next().is_some() && world.time_passed < config.batch.max_steps {
+                world.advance()?;
+            }
+            survived += world
+                .inhabitants
+                .iter()
+                .map(|inhabitant| inhabitant.state.time_passed)
+                .sum::<usize>();
+            trades += world.market.trades.len();
+        }
+        writeln!(
+            out,
+            "{}",
+            tr!(
+                "report.trading.row",
+                strategy = name,
+                crowd = CROWD,
+                survival = survived as f64 / (WORLDS as usize * CROWD) as f64,
+                trades = trades as f64 / WORLDS as f64
+            )
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Население Дурляндии с рождениями и размножением.
+pub fn report_demography(out: &mut dyn Write, path: &Path) -> Result<(), Box<dyn Error>> {
+    const STEPS: usize = 300;
+    const INITIAL_POPULATION: usize = 30;
+
+    let config = GeneticConfig::default();
+    let (robust, _) = optimize_robust(&config, RobustObjective::Mean)?;
+    let genome = robust.genome;
+
+    let mut world = World::new(1337);
+    let demography = Reproduction::new(25.0, 0.05).and_then(|reproduction| {
+        Demography::new(
+            0.3,
+            Some(reproduction),
+            200,
+            Box::new(move || {
+                Box::new(RuleStrategy {
+                    genome: genome.clone(),
+                })
+            }),
+        )
+    });
+    let demography = demography?;
+    for i in 0..INITIAL_POPULATION {
+        let strategy = demography.new_strategy();
+        world.add_inhabitant(tr!("world.inhabitant", n = i + 1), None, strategy)?;
+    }
+    world.demography = Some(demography);
+
+    while world.time_passed < STEPS {
+        world.advance()?;
+    }
+
+    write!(
+        out,
+        "{:>8} {:>8}",
+        text("report.column.time"),
+        text("report.column.total")
+    )?;
+    for race in RACES.iter() {
+        write!(out, " {:>10}", race.name())?;
+    }
+    writeln!(out)?;
+    for census in world.census.iter().step_by(20) {
+        write!(out, "{:>8} {:>8}", census.time, census.total())?;
+        for race in RACES.iter() {
+            write!(out, " {:>10}", census.race(race.id))?;
+        }
+        writeln!(out)?;
+    }
+
+    write_census_csv(&world.census, path)
+        .map_err(|error| tr!("report.save_csv_failed", error = error))?;
+    writeln!(
+        out,
+        "{}",
+        tr!("report.demography.saved", path = path.display())
+    )?;
+
+    Ok(())
+}
+
+/// Прогоняет стандартные стратегии за каждую нацию с проверкой инвариантов,
+/// пишет найденные нарушения в `out` и возвращает их число.
+pub fn check_invariants(out: &mut dyn Write) -> io::Result<usize> {
+    let batch = BatchConfig {
+        check_invariants: true,
+        ..Default::default()
+    };
+    let strategies: [&dyn Strategy; 2] = [&ApatheticStrategy, &RandomStrategy];
+
+    let mut violations = 0;
+    for nation in NATIONS.iter() {
+        for strategy in strategies {
+            let result = run_batch(strategy, Some(nation), &batch);
+            writeln!(
+                out,
+                "{}",
+                tr!(
+                    "report.check.row",
+                    nation = nation.name_plural(),
+                    strategy = strategy.name(),
+                    ok = result.outcomes.iter().filter(|o| !o.failed).count(),
+                    errors = result.errors.len()
+                )
+            )?;
+            for error in &result.errors {
+                writeln!(out, "    {error}")?;
+            }
+            violations += result.errors.len();
+        }
+    }
+
+    Ok(violations)
+}
+
+/// Заголовок раздела отчёта: `title` посередине строки из дефисов.
+fn header(out: &mut dyn Write, title: impl std::fmt::Display) -> io::Result<()> {
+    writeln!(out, "{:-^80}", format!(" {title} "))
+}
@@ -0,0 +1,118 @@
+use crate::{
+    definitions::{ResourceMap, SimError, SimErrorKind},
+    i18n::text_or,
+    ids::ResourceId,
+    registry::{content, ContentKind},
+};
+use lazy_static::lazy_static;
+
+/// Здоровье.
+pub const HEALTH: ResourceId = ResourceId("health");
+
+/// Деньги.
+pub const MONEY: ResourceId = ResourceId("money");
+
+/// Удовлетворённость жизнью.
+pub const SATISFACTION: ResourceId = ResourceId("satisfaction");
+
+/// Жизненный ресурс Игрока и его свойства.
+#[derive(Debug, Clone)]
+pub struct Resource {
+    pub id: ResourceId,
+
+    /// Название на языке по умолчанию.
+    /// Перевод - в каталоге сообщений по ключу `resource.<id>.name`.
+    pub name: &'static str,
+
+    /// Игрок погибает, если ресурса становится столько или меньше.
+    pub death_threshold: f64,
+
+    /// Количество ресурса у только что родившегося Игрока.
+    pub starting_amount: f64,
+
+    /// Доля ресурса, которую Игрок теряет за каждый временной интервал,
+    /// чем бы он ни занимался.
+    pub decay: f64,
+
+    /// Сколько ресурса расходует безделье, в долях `Param::IdleCost`.
+    pub idle_cost: f64,
+}
+
+lazy_static! {
+    /// Все ресурсы из установленного содержимого игры (`content()`).
+    /// Игрок, отчёты и правила симуляции перебирают ресурсы сами, поэтому
+    /// чтобы добавить ресурс, достаточно описать его в наборе содержимого.
+    pub static ref RESOURCES: &'static [Resource] =
+        content().map(|content| content.resources.as_slice()).unwrap_or_default();
+}
+
+/// Ресурсы базового набора содержимого.
+pub fn base_resources() -> Vec<Resource> {
+    vec![
+        Resource {
+            id: HEALTH,
+            name: "Здоровье",
+            death_threshold: 0.0,
+            starting_amount: 10.0,
+            decay: 0.0,
+            idle_cost: 1.0,
+        },
+        Resource {
+            id: MONEY,
+            name: "Деньги",
+            death_threshold: 0.0,
+            starting_amount: 10.0,
+            decay: 0.0,
+            idle_cost: 1.0,
+        },
+        Resource {
+            id: SATISFACTION,
+            name: "Удовл. жизнью",
+            death_threshold: 0.0,
+            starting_amount: 10.0,
+            decay: 0.0,
+            idle_cost: 1.0,
+        },
+    ]
+}
+
+impl Resource {
+    /// Название ресурса на текущем языке.
+    pub fn name(&self) -> &'static str {
+        text_or(&format!("resource.{}.name", self.id), self.name)
+    }
+}
+
+impl ResourceId {
+    pub fn try_get(self) -> Result<&'static Resource, SimError> {
+        RESOURCES.iter().find(|res| res.id == self).ok_or_else(|| {
+            SimError::new(SimErrorKind::UnknownId {
+                kind: ContentKind::Resource,
+                id: self.to_string(),
+            })
+        })
+    }
+
+    /// Название ресурса на текущем языке, а если такого ресурса нет
+    /// в содержимом игры - его идентификатор.
+    pub fn name(self) -> &'static str {
+        self.try_get().map_or(self.0, Resource::name)
+    }
+}
+
+/// Ресурсы только что родившегося Игрока.
+pub fn starting_resources() -> ResourceMap {
+    RESOURCES
+        .iter()
+        .map(|res| (res.id, res.starting_amount))
+        .collect()
+}
+
+/// `ResourceMap` с указанными количествами ресурсов (остальные - нули).
+pub fn amounts(pairs: &[(ResourceId, f64)]) -> ResourceMap {
+    let mut map = ResourceMap::default();
+    for &(resource, amount) in pairs {
+        map[resource] += amount;
+    }
+    map
+}
@@ -0,0 +1,152 @@
+use crate::{
+    batch::{BatchResult, GameOutcome},
+    i18n::{text, tr},
+};
+use std::fmt;
+
+/// Критерий, по которому сравниваются стратегии.
+///
+/// Среднее время жизни скрывает редкие, но катастрофические исходы
+/// (например, потерю половины денег в Пунта-пеликане), поэтому
+/// планировщики могут оптимизировать и меры риска.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Objective {
+    /// Среднее время жизни.
+    MeanSurvival,
+
+    /// Вероятность того, что какой-либо ресурс опустится до `threshold`
+    /// или ниже в течение первых `horizon` временных интервалов.
+    /// Чем она меньше, тем лучше.
+    ProbabilityOfRuin { threshold: f64, horizon: usize },
+
+    /// CVaR минимального ресурса: среднее значение самого дефицитного
+    /// ресурса за всю симуляцию в доле `alpha` худших симуляций.
+    CVaR { alpha: f64 },
+
+    /// Детерминированный эквивалент экспоненциальной полезности времени жизни
+    /// с коэффициентом неприятия риска `lambda`.
+    RiskAverse { lambda: f64 },
+}
+
+/// Меры риска пакетного прогона.
+pub struct RiskReport {
+    pub mean_survival: f64,
+    pub probability_of_ruin: f64,
+    pub cvar: f64,
+    pub certainty_equivalent: f64,
+}
+
+impl Objective {
+    /// Оценка стратегии по результатам пакетного прогона.
+    /// Чем больше значение, тем лучше стратегия.
+    pub fn score(self, result: &BatchResult) -> f64 {
+        match self {
+            Objective::MeanSurvival => result.mean_survival(),
+            Objective::ProbabilityOfRuin { threshold, horizon } => {
+                -probability_of_ruin(result, threshold, horizon)
+            }
+            Objective::CVaR { alpha } => cvar_of_min_resource(result, alpha),
+            Objective::RiskAverse { lambda } => certainty_equivalent(result, lambda),
+        }
+    }
+}
+
+impl fmt::Display for Objective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Objective::MeanSurvival => write!(f, "{}", text("objective.mean_survival")),
+            Objective::ProbabilityOfRuin { threshold, horizon } => write!(
+                f,
+                "{}",
+                tr!(
+                    "objective.probability_of_ruin",
+                    threshold = threshold,
+                    horizon = horizon
+                )
+            ),
+            Objective::CVaR { alpha } => {
+                write!(f, "{}", tr!("objective.cvar", alpha = alpha))
+            }
+            Objective::RiskAverse { lambda } => {
+                write!(f, "{}", tr!("objective.risk_averse", lambda = lambda))
+            }
+        }
+    }
+}
+
+impl RiskReport {
+    pub fn new(result: &BatchResult, threshold: f64, horizon: usize, alpha: f64, lambda: f64) -> Self {
+        Self {
+            mean_survival: result.mean_survival(),
+            probability_of_ruin: probability_of_ruin(result, threshold, horizon),
+            cvar: cvar_of_min_resource(result, alpha),
+            certainty_equivalent: certainty_equivalent(result, lambda),
+        }
+    }
+}
+
+/// Доля симуляций, в которых какой-либо ресурс опустился до `threshold`
+/// или ниже в течение первых `horizon` временных интервалов.
+pub fn probability_of_ruin(result: &BatchResult, threshold: f64, horizon: usize) -> f64 {
+    if result.outcomes.is_empty() {
+        return 0.0;
+    }
+
+    let ruined = result
+        .outcomes
+        .iter()
+        .filter(|o| o.min_resource_trace.iter().take(horizon).any(|&x| x <= threshold))
+        .count();
+
+    ruined as f64 / result.outcomes.len() as f64
+}
+
+/// CVaR минимального ресурса: среднее по доле `alpha` худших симуляций
+/// от самого маленького значения ресурса, встреченного за симуляцию.
+pub fn cvar_of_min_resource(result: &BatchResult, alpha: f64) -> f64 {
+    let mut minima: Vec<f64> = result.outcomes.iter().map(lowest_resource).collect();
+    if minima.is_empty() {
+        return 0.0;
+    }
+    minima.sort_by(f64::total_cmp);
+
+    let tail = ((minima.len() as f64 * alpha).ceil() as usize).clamp(1, minima.len());
+    minima[..tail].iter().sum::<f64>() / tail as f64
+}
+
+/// Детерминированный эквивалент экспоненциальной полезности `-exp(-lambda * x)`
+/// времени жизни `x`: такое время жизни, получаемое наверняка, которое
+/// не склонный к риску Игрок считает равноценным лотерее из пакетного прогона.
+/// При `lambda == 0` совпадает со средним временем жизни.
+pub fn certainty_equivalent(result: &BatchResult, lambda: f64) -> f64 {
+    if lambda == 0.0 || result.outcomes.is_empty() {
+        return result.mean_survival();
+    }
+
+    // Сдвигаем времена жизни так, чтобы показатель экспоненты был не больше нуля
+    // и она не переполнялась на длинных симуляциях: при неприятии риска
+    // вычитаем минимум, а при склонности к риску (`lambda < 0`) - максимум.
+    let steps = result.outcomes.iter().map(|o| o.steps_survived as f64);
+    let shift = if lambda > 0.0 {
+        steps.fold(f64::INFINITY, f64::min)
+    } else {
+        steps.fold(f64::NEG_INFINITY, f64::max)
+    };
+
+    let expected = result
+        .outcomes
+        .iter()
+        .map(|o| (-lambda * (o.steps_survived as f64 - shift)).exp())
+        .sum::<f64>()
+        / result.outcomes.len() as f64;
+
+    shift - expected.ln() / lambda
+}
+
+fn lowest_resource(outcome: &GameOutcome) -> f64 {
+    outcome
+        .min_resource_trace
+        .iter()
+        .copied()
+        .fold(f64::INFINITY, f64::min)
+}
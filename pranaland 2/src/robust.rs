@@ -0,0 +1,120 @@
+use crate::{
+    batch::{run_batch, BatchConfig},
+    definitions::{SimError, Strategy},
+    genetic::{evolve, nation_fitness, Evolved, GeneticConfig},
+    i18n::localized_display,
+    nations::{Nation, NATIONS},
+    registry::{missing, ContentKind},
+    risk::Objective,
+    strategies::RuleStrategy,
+};
+
+/// Способ свести оценки стратегии по всем нациям в одно число.
+///
+/// Игрок не знает своей нации, поэтому оптимизировать имеет смысл
+/// одну стратегию сразу для всех наций.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RobustObjective {
+    /// Среднее по всем нациям.
+    Mean,
+
+    /// Оценка для самой неудачливой нации.
+    WorstCase,
+}
+
+// Названия критериев - в каталогах сообщений (`i18n`).
+localized_display!(RobustObjective => "robust_objective");
+
+/// Сожаление о незнании своей нации.
+pub struct NationRegret {
+    pub nation: &'static Nation,
+
+    /// Оценка робастной стратегии.
+    pub robust: f64,
+
+    /// Оценка стратегии, подобранной именно для этой нации.
+    ///
+    /// Это то, что нашёл ГА, без поправок: если робастная стратегия
+    /// оказалась лучше, сожаление отрицательно (см. [`NationRegret::search_failed`]).
+    pub optimum: f64,
+}
+
+impl RobustObjective {
+    /// Сводит приспособленность по нациям (в порядке `NATIONS`) в одно число.
+    /// Если наций нет, сводить нечего - `None`.
+    pub fn combine(self, per_nation: &[f64]) -> Option<f64> {
+        match self {
+            RobustObjective::Mean => (!per_nation.is_empty())
+                .then(|| per_nation.iter().sum::<f64>() / per_nation.len() as f64),
+            RobustObjective::WorstCase => per_nation.iter().copied().reduce(f64::min),
+        }
+    }
+}
+
+impl NationRegret {
+    /// Во сколько Игроку обходится незнание своей нации.
+    pub fn regret(&self) -> f64 {
+        self.optimum - self.robust
+    }
+
+    /// ГА не нашёл для нации стратегии даже такой же хорошей, как
+    /// робастная, хотя робастная для неё тоже допустима. Такое сожаление
+    /// говорит о неудачном поиске, а не о ценности знания своей нации.
+    pub fn search_failed(&self) -> bool {
+        self.regret() < 0.0
+    }
+}
+
+/// Оценка стратегии `strategy` по критерию `objective` для каждой нации
+/// (в порядке `NATIONS`).
+pub fn per_nation_fitness(
+    strategy: &dyn Strategy,
+    batch: &BatchConfig,
+    objective: Objective,
+) -> Vec<f64> {
+    NATIONS
+        .iter()
+        .map(|nation| objective.score(&run_batch(strategy, Some(nation), batch)))
+        .collect()
+}
+
+/// Подбирает одну стратегию на правилах для всех наций сразу и считает для
+/// каждой нации сожаление относительно стратегии, подобранной только для неё.
+/// Ошибка - если в содержимом игры нет ни одной нации.
+pub fn optimize_robust(
+    config: &GeneticConfig,
+    objective: RobustObjective,
+) -> Result<(Evolved, Vec<NationRegret>), SimError> {
+    if NATIONS.is_empty() {
+        return Err(missing(ContentKind::Nation));
+    }
+
+    let robust = evolve(config, &|genome| {
+        let strategy = RuleStrategy {
+            genome: genome.clone(),
+        };
+        let per_nation = per_nation_fitness(&strategy, &config.batch, config.objective);
+        objective.combine(&per_nation).unwrap_or(f64::NEG_INFINITY)
+    });
+
+    let robust_strategy = RuleStrategy {
+        genome: robust.genome.clone(),
+    };
+
+    let regrets = NATIONS
+        .iter()
+        .zip(per_nation_fitness(&robust_strategy, &config.batch, config.objective))
+        .map(|(nation, robust)| {
+            let optimum = evolve(config, &|genome| {
+                nation_fitness(genome, nation, config)
+            });
+            NationRegret {
+                nation,
+                robust,
+                optimum: optimum.fitness,
+            }
+        })
+        .collect();
+
+    Ok((robust, regrets))
+}
@@ -0,0 +1,79 @@
+use crate::params::{default_params, Param, ParamMap};
+use strum::IntoEnumIterator;
+
+/// Множители значения по умолчанию, которые перебираются для каждого параметра.
+pub const DEFAULT_FACTORS: [f64; 5] = [0.5, 0.75, 1.0, 1.25, 1.5];
+
+/// Результат оценки при одном значении параметра.
+pub struct SweepPoint {
+    pub value: f64,
+
+    /// Оценка каждой из сравниваемых стратегий.
+    pub scores: Vec<f64>,
+}
+
+/// Как оценки стратегий зависят от одного параметра.
+pub struct ParamSensitivity {
+    pub param: Param,
+    pub points: Vec<SweepPoint>,
+}
+
+impl ParamSensitivity {
+    /// Наибольший по всем стратегиям разброс оценки при изменении параметра.
+    pub fn swing(&self) -> f64 {
+        let strategies = self.points.first().map_or(0, |p| p.scores.len());
+        (0..strategies)
+            .map(|i| {
+                let scores = self.points.iter().map(|p| p.scores[i]);
+                let max = scores.clone().fold(f64::NEG_INFINITY, f64::max);
+                let min = scores.fold(f64::INFINITY, f64::min);
+                max - min
+            })
+            .fold(0.0, f64::max)
+    }
+}
+
+/// Значения параметра: значение по умолчанию, умноженное на каждый из `factors`.
+/// Вероятности не выходят за пределы `0.0..=1.0`.
+pub fn sweep_values(param: Param, factors: &[f64]) -> Vec<f64> {
+    factors
+        .iter()
+        .map(|factor| {
+            let value = param.default_value() * factor;
+            if param.is_probability() {
+                value.clamp(0.0, 1.0)
+            } else {
+                value
+            }
+        })
+        .collect()
+}
+
+/// Изменяет по очереди каждый параметр (остальные остаются по умолчанию)
+/// и оценивает стратегии функцией `evaluate`. Она может как просто прогонять
+/// готовые стратегии, так и заново подбирать их для новых правил.
+/// Результат отсортирован по убыванию `ParamSensitivity::swing`.
+pub fn analyze_sensitivity(
+    factors: &[f64],
+    evaluate: &dyn Fn(&ParamMap) -> Vec<f64>,
+) -> Vec<ParamSensitivity> {
+    let mut report: Vec<ParamSensitivity> = Param::iter()
+        .map(|param| {
+            let points = sweep_values(param, factors)
+                .into_iter()
+                .map(|value| {
+                    let mut params = default_params();
+                    params[param] = value;
+                    SweepPoint {
+                        value,
+                        scores: evaluate(&params),
+                    }
+                })
+                .collect();
+            ParamSensitivity { param, points }
+        })
+        .collect();
+
+    report.sort_by(|a, b| b.swing().total_cmp(&a.swing()));
+    report
+}
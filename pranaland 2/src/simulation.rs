@@ -1,45 +1,338 @@
 use crate::{
+    calendar::GlobalEvent,
     definitions::*,
+    fauna::FAUNA,
+    invariants,
     locations::{random_location, Location},
     nations::{random_nation, Nation},
+    ids::{FaunaId, OccupationId, ParamId},
+    occupations::{Occupation, OCCUPATIONS},
+    params::{default_content_params, default_params, Param},
+    registry::{content, ContentKind},
+    resources::RESOURCES,
+    travel::{find_route, routes_from, Route},
+    world::WorldState,
 };
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha8Rng;
-
-use strum::IntoEnumIterator;
-use Fauna::*;
-use Occupation::*;
-use Resource::*;
+use std::panic::{self, AssertUnwindSafe};
 
 impl GameState {
-    pub fn new(name: String, nationality: Option<&'static Nation>, seed: u64) -> Self {
+    /// Новая игра Игрока `name` в случайной локации. Если нация не задана,
+    /// она тоже выбирается случайно. Ошибка - если содержимое игры
+    /// некорректно (см. `content`).
+    pub fn new(
+        name: String,
+        nationality: Option<&'static Nation>,
+        seed: u64,
+    ) -> Result<Self, SimError> {
         let mut rng = Box::new(ChaCha8Rng::seed_from_u64(seed));
-        let location = random_location(&mut rng);
-        let nationality = nationality.unwrap_or_else(|| random_nation(&mut rng));
+        let location = random_location(&mut rng)?;
+        let nationality = match nationality {
+            Some(nationality) => nationality,
+            None => random_nation(&mut rng)?,
+        };
+        let world = WorldState::new();
 
-        Self {
+        Ok(Self {
             rng,
+            seed,
             player: Player::new(name, nationality),
             location,
             location_history: vec![location],
-            effective_fauna: FaunaMap::default(),
+            effective_fauna: world.fauna_in(location)?,
+            declared_fauna: FaunaMap::default(),
+            world,
             resource_change: ResourceMap::default(),
             time_spent_in_this_location: 0,
             time_passed: 0,
-        }
+            transit: None,
+            crowding: 1,
+            steps_world: true,
+            attribution: Vec::new(),
+            params: default_params(),
+            content_params: default_content_params(),
+            verbose: true,
+            events: None,
+            check_invariants: false,
+        })
     }
 
     pub fn chance(&mut self, probability: f64) -> bool {
         self.rng.gen::<f64>() <= probability
     }
 
-    pub fn advance(&mut self, strategy: &dyn Strategy) {
-        assert!(self.resource_change.values().all(|&res| res == 0.0));
+    /// Ошибка `kind`, произошедшая с Игроком на текущем временном интервале.
+    pub fn error(&self, kind: SimErrorKind) -> SimError {
+        SimError {
+            kind,
+            context: Some(SimContext {
+                step: self.time_passed,
+                location: self.location.id,
+                nation: self.player.nationality.id,
+            }),
+        }
+    }
+
+    /// Спрашивает у стратегии действие на текущем интервале. Если стратегия
+    /// паникует, возвращает ошибку вместо того, чтобы прервать всю программу.
+    pub fn choose_action(&mut self, strategy: &dyn Strategy) -> Result<Action, SimError> {
+        panic::catch_unwind(AssertUnwindSafe(|| strategy.take_action(self))).map_err(|payload| {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            self.error(SimErrorKind::StrategyPanicked {
+                strategy: strategy.name(),
+                message,
+            })
+        })
+    }
+
+    /// Проверяет, может ли Игрок совершить действие `action`, и возвращает
+    /// маршрут, по которому он отправится в другую локацию (если отправится).
+    pub fn validate_action(&self, action: &Action) -> Result<Option<&'static Route>, ActionError> {
+        if self.player.is_dead() {
+            return Err(ActionError::PlayerDead);
+        }
+
+        let route = match action.destination.filter(|&d| d != self.location) {
+            Some(dest) => Some(find_route(self.location, dest).ok_or(ActionError::NoRoute {
+                from: self.location.id,
+                to: dest.id,
+            })?),
+            None => None,
+        };
+
+        if let Some(route) = route {
+            for (resource, &required) in route.cost.iter().filter(|(_, &cost)| cost > 0.0) {
+                let available = self.player.resources[resource];
+                if self.player.margin(resource) <= required {
+                    return Err(ActionError::CannotAffordTravel {
+                        resource,
+                        required,
+                        available,
+                    });
+                }
+            }
+        }
+
+        if let Some(occupation) = action.occupation {
+            let (location, available) = match route {
+                Some(route) => {
+                    let available = self.world.fauna_in(route.to).map_err(|_| {
+                        ActionError::UnknownLocation {
+                            location: route.to.id,
+                        }
+                    })?;
+                    (route.to, available)
+                }
+                None => (self.location, self.effective_fauna.clone()),
+            };
+            self.validate_occupation(occupation, location, &available)?;
+        }
+
+        Ok(route)
+    }
+
+    /// Проверяет, может ли Игрок предаться занятию `occupation` в локации
+    /// `location`, где работает фауна `available`.
+    fn validate_occupation(
+        &self,
+        occupation: OccupationId,
+        location: &'static Location,
+        available: &FaunaMap,
+    ) -> Result<(), ActionError> {
+        let info = occupation
+            .try_get()
+            .map_err(|_| ActionError::UnknownOccupation { occupation })?;
+        if let Some(&(fauna, _)) = info.fauna.first() {
+            if info.fauna.iter().all(|&(fauna, _)| available[fauna] == 0) {
+                return Err(ActionError::NoFauna {
+                    occupation,
+                    fauna,
+                    location: location.id,
+                });
+            }
+        }
+        if let Some(prerequisite) = &info.prerequisite {
+            if !(prerequisite.check)(self, location) {
+                return Err(ActionError::PrerequisiteNotMet {
+                    occupation,
+                    requirement: prerequisite.description,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Все действия, которые Игрок может совершить на текущем временном интервале:
+    /// каждая доступная локация × каждое занятие (включая безделье).
+    pub fn legal_actions(&self) -> Vec<Action> {
+        let occupations: Vec<Option<OccupationId>> = std::iter::once(None)
+            .chain(OCCUPATIONS.iter().map(|occupation| Some(occupation.id)))
+            .collect();
+
+        self.legal_destinations()
+            .into_iter()
+            .flat_map(|destination| {
+                occupations.iter().map(move |&occupation| Action {
+                    destination: Some(destination),
+                    occupation,
+                })
+            })
+            .filter(|action| self.validate_action(action).is_ok())
+            .collect()
+    }
+
+    /// Проводит Игрока через очередной временной интервал.
+    /// Если стратегия выбрала недопустимое действие или запаниковала,
+    /// интервал не начинается: время, локация и ресурсы Игрока остаются
+    /// прежними. Но стратегия получает `&mut GameState` и до ошибки могла
+    /// изменить состояние - например, израсходовать случайные числа `rng`, -
+    /// поэтому, чтобы повторить интервал с другой стратегией с того же места,
+    /// клонируйте состояние до вызова. Если ошибка произошла позже,
+    /// продолжать эту игру нельзя.
+    ///
+    /// При `check_invariants` после интервала проверяется целостность
+    /// состояния, и нарушения возвращаются как `SimErrorKind::InvariantViolated`.
+    pub fn advance(&mut self, strategy: &dyn Strategy) -> Result<(), SimError> {
+        if !self.check_invariants {
+            return self.advance_unchecked(strategy);
+        }
+
+        let before = self.clone();
+        self.advance_unchecked(strategy)?;
+        invariants::check(&before, self, strategy)
+    }
+
+    /// Эффект, увеличивающий численность работающей фауны `effective_fauna`,
+    /// объявляет прибавку этим методом - иначе проверка инвариантов сочтёт
+    /// её ошибкой.
+    pub fn declare_fauna(&mut self, fauna: FaunaId, count: usize) {
+        self.effective_fauna[fauna] += count;
+        self.declared_fauna[fauna] += count;
+    }
+
+    /// Доход от одной особи фауны `fauna` при занятии `occupation` в текущей
+    /// локации: `FaunaYield` с множителями занятия и времени года, поделённый
+    /// между Игроками в локации.
+    pub fn yield_per_head(&self, occupation: &Occupation, fauna: FaunaId) -> f64 {
+        let factor: f64 = occupation
+            .fauna
+            .iter()
+            .filter(|&&(id, _)| id == fauna)
+            .map(|&(_, factor)| factor)
+            .sum();
+        let seasonal = self
+            .world
+            .income_factor(self.location.biome, occupation.produces);
+        factor * self.params[Param::FaunaYield] * seasonal / self.crowding.max(1) as f64
+    }
+
+    /// Значение параметра `id`, объявленного содержимым игры (`ParamDef`).
+    /// Ошибка - если такого параметра нет.
+    pub fn param(&self, id: ParamId) -> Result<f64, SimError> {
+        self.content_params
+            .iter()
+            .find(|&(param, _)| param == id)
+            .map(|(_, &value)| value)
+            .ok_or_else(|| {
+                self.error(SimErrorKind::UnknownId {
+                    kind: ContentKind::Param,
+                    id: id.to_string(),
+                })
+            })
+    }
+
+    fn advance_unchecked(&mut self, strategy: &dyn Strategy) -> Result<(), SimError> {
+        if self.resource_change.values().any(|&res| res != 0.0) {
+            return Err(self.error(SimErrorKind::UnfinishedInterval));
+        }
+        if let Some((param, &value)) = self
+            .params
+            .iter()
+            .find(|(param, &value)| !param.accepts(value))
+        {
+            return Err(self.error(SimErrorKind::InvalidParam { param, value }));
+        }
+        let defs = &content()?.params;
+        if let Some((param, &value)) = self.content_params.iter().find(|&(id, &value)| {
+            defs.iter().any(|def| def.id == id && !def.accepts(value))
+        }) {
+            return Err(self.error(SimErrorKind::InvalidContentParam { param, value }));
+        }
+        if let Some((field, value)) = self
+            .world
+            .default_dynamics
+            .and_then(|dynamics| dynamics.invalid_fields().into_iter().next())
+        {
+            return Err(self.error(SimErrorKind::InvalidDynamics { field, value }));
+        }
+        // Проверка действия этого не заметит, если Игрок погиб в пути.
+        if self.player.is_dead() {
+            return Err(self.error(SimErrorKind::IllegalAction(ActionError::PlayerDead)));
+        }
+
+        let action = match self.transit.take() {
+            Some(transit) if transit.remaining > 0 => {
+                self.begin_interval();
+                return self.travel(Transit {
+                    remaining: transit.remaining - 1,
+                    ..transit
+                });
+            }
+            // Игрок прибывает на место, дорога уже оплачена. Занятие было
+            // проверено до отправления, а за время пути фауна в локации
+            // назначения могла измениться, поэтому оно проверяется заново.
+            Some(transit) => {
+                self.begin_interval();
+                let destination = transit.destination;
+                let mut action = Action {
+                    destination: Some(destination),
+                    ..transit.action
+                };
+                if let Some(occupation) = action.occupation {
+                    let available = self.world.fauna_in(destination)?;
+                    if let Err(error) = self.validate_occupation(occupation, destination, &available) {
+                        narrate!(self, "simulation.arrival_idle", name = self.player.name, error = error);
+                        action.occupation = None;
+                    }
+                }
+                action
+            }
+            None => {
+                let action = self.choose_action(strategy)?;
+                let route = self
+                    .validate_action(&action)
+                    .map_err(|error| self.error(SimErrorKind::IllegalAction(error)))?;
+
+                self.begin_interval();
+
+                if let Some(route) = route {
+                    self.attributed(EffectSource::Travel, |state| {
+                        for (res, &cost) in &route.cost {
+                            state.resource_change[res] -= cost;
+                        }
+                        Ok(())
+                    })?;
 
-        let action = strategy.take_action(self);
+                    if route.extra_intervals > 0 {
+                        return self.travel(Transit {
+                            action,
+                            destination: route.to,
+                            remaining: route.extra_intervals - 1,
+                        });
+                    }
+                }
+
+                action
+            }
+        };
 
         let new_location = action.destination.and_then(|dest| {
-            if (dest as *const Location) == (self.location as *const Location) {
+            if dest == self.location {
                 None
             } else {
                 Some(dest)
@@ -47,26 +340,47 @@ impl GameState {
         });
 
         if let Some(dest) = new_location {
-            println!(
-                "{} решает сменить локацию: {} -> {}",
-                self.player.name, self.location.name, dest.name
+            narrate!(
+                self,
+                "simulation.move",
+                name = self.player.name,
+                from = self.location.name(),
+                to = dest.name()
             );
 
-            println!("{dest}");
+            narrate!(self, "simulation.location", location = dest.describe(&self.params, &self.content_params));
 
             self.location = dest;
             self.location_history.push(dest);
             self.time_spent_in_this_location = 0;
-            self.effective_fauna = dest.fauna.clone();
+            self.effective_fauna = self.world.fauna_in(dest)?;
+
+            if self.effective_fauna != dest.fauna {
+                let fauna: Vec<String> = FAUNA
+                    .iter()
+                    .map(|f| format!("{}: {}", f.name(), self.effective_fauna[f.id]))
+                    .collect();
+                narrate!(self, "simulation.fauna_now", fauna = fauna.join(", "));
+            }
 
             if self.location_history.len() > MAX_LOCATION_HISTORY_LEN {
                 let new_start = self.location_history.len() - MAX_LOCATION_HISTORY_LEN;
                 self.location_history.drain(0..new_start);
             }
         } else {
-            println!("{} остаётся в {}", self.player.name, self.location.name,);
+            narrate!(
+                self,
+                "simulation.stay",
+                name = self.player.name,
+                location = self.location.name()
+            );
 
             self.time_spent_in_this_location += 1;
+
+            let available = self.world.fauna_in(self.location)?;
+            for (fauna, count) in self.effective_fauna.iter_mut() {
+                *count = (*count).min(available[fauna]);
+            }
         }
 
         // Пока что эффекты от локации всегда применяются до эффектов от национальности.
@@ -74,53 +388,196 @@ impl GameState {
         // Оставим рассуждения о том, к каким проблемам это может привести и как их избежать
         // читателю в качестве упражнения :о)
         if let Some(pre) = self.location.pre_effect {
-            pre(self);
+            self.attributed(EffectSource::Location(self.location.id), pre)?;
         }
         if let Some(pre) = self.player.nationality.pre_effect {
-            pre(self);
+            self.attributed(EffectSource::Nation(self.player.nationality.id), pre)?;
         }
 
-        if let Some(occupation) = action.occupation {
-            println!("{} решает {}.", self.player.name, occupation);
+        self.attributed(EffectSource::Occupation, |state| {
+            if let Some(occupation) = action.occupation {
+                narrate!(
+                    state,
+                    "simulation.occupation",
+                    name = state.player.name,
+                    occupation = occupation.name()
+                );
 
-            for res in self.resource_change.values_mut() {
-                *res = -1.0;
-            }
+                let info = occupation.try_get()?;
+                let cost = state.params[Param::OccupationCost];
+                let per_head: f64 = info
+                    .fauna
+                    .iter()
+                    .map(|&(fauna, factor)| state.effective_fauna[fauna] as f64 * factor)
+                    .sum();
+                let seasonal = state
+                    .world
+                    .income_factor(state.location.biome, info.produces);
+                let income = per_head * state.params[Param::FaunaYield] * seasonal
+                    / state.crowding.max(1) as f64;
+                if let Some(calendar) = state.world.calendar.clone().filter(|_| seasonal != 1.0) {
+                    narrate!(
+                        state,
+                        "simulation.seasonal",
+                        calendar = calendar,
+                        factor = seasonal
+                    );
+                }
+                if state.crowding > 1 {
+                    narrate!(state, "simulation.crowding", n = state.crowding);
+                }
 
-            let (target_resource, target_fauna) = match occupation {
-                Zoombaling => (Money, Slesandra),
-                Goolboning => (Satisfaction, Sisyandra),
-                Shlamsing => (Health, Chuchundra),
-            };
+                for (res, &amount) in &info.consumes {
+                    state.resource_change[res] -= amount * cost;
+                }
+                state.resource_change[info.produces] += income;
 
-            self.resource_change[target_resource] = self.effective_fauna[target_fauna] as f64 * 2.0;
-        } else {
-            println!("{} не делает ничего.", self.player.name);
+                for &(fauna, _) in &info.fauna {
+                    state.world.exploit(state.location, fauna)?;
+                }
+            } else {
+                narrate!(state, "simulation.idle", name = state.player.name);
 
-            for res in self.resource_change.values_mut() {
-                *res = -0.5;
+                let cost = state.params[Param::IdleCost];
+                for res in RESOURCES.iter() {
+                    state.resource_change[res.id] -= res.idle_cost * cost;
+                }
+            }
+            Ok(())
+        })?;
+
+        for fauna in FAUNA.iter() {
+            let behaviours = fauna.behaviours.iter().chain(
+                self.location
+                    .fauna_behaviours
+                    .iter()
+                    .filter(|(f, _)| *f == fauna.id)
+                    .map(|(_, behaviour)| behaviour),
+            );
+            for behaviour in behaviours {
+                self.attributed(EffectSource::Fauna(fauna.id), |state| {
+                    behaviour.apply(fauna.id, state);
+                    Ok(())
+                })?;
             }
         }
 
         if let Some(post) = self.location.post_effect {
-            post(self, &action);
+            self.attributed(EffectSource::Location(self.location.id), |state| {
+                post(state, &action)
+            })?;
         }
         if let Some(post) = self.player.nationality.post_effect {
-            post(self, &action);
+            self.attributed(EffectSource::Nation(self.player.nationality.id), |state| {
+                post(state, &action)
+            })?;
         }
 
-        println!(
-            "Результаты: {Health} {:+.2}, {Money} {:+.2}, {Satisfaction} {:+.2}",
-            self.resource_change[Health],
-            self.resource_change[Money],
-            self.resource_change[Satisfaction],
+        self.finish_interval()
+    }
+
+    /// Сбрасывает то, что копится в `GameState` за один временной интервал.
+    fn begin_interval(&mut self) {
+        self.attribution.clear();
+        self.declared_fauna = FaunaMap::default();
+    }
+
+    /// Локации, в которые Игрок может отправиться из текущей (включая её саму).
+    pub fn legal_destinations(&self) -> Vec<&'static Location> {
+        std::iter::once(self.location)
+            .chain(routes_from(self.location).map(|route| route.to))
+            .collect()
+    }
+
+    /// Временной интервал, который Игрок проводит в пути.
+    fn travel(&mut self, transit: Transit) -> Result<(), SimError> {
+        narrate!(
+            self,
+            "simulation.transit",
+            name = self.player.name,
+            from = self.location.name(),
+            to = transit.destination.name()
         );
 
-        for resource in Resource::iter() {
-            self.player.resources[resource] += self.resource_change[resource];
-            self.resource_change[resource] = 0.0;
+        self.attributed(EffectSource::Travel, |state| {
+            let cost = state.params[Param::IdleCost];
+            for res in RESOURCES.iter() {
+                state.resource_change[res.id] -= res.idle_cost * cost;
+            }
+            Ok(())
+        })?;
+
+        self.transit = Some(transit);
+        self.finish_interval()
+    }
+
+    /// Применяет накопленные за интервал изменения ресурсов и переходит
+    /// к следующему интервалу.
+    fn finish_interval(&mut self) -> Result<(), SimError> {
+        self.attributed(EffectSource::Decay, |state| {
+            for resource in RESOURCES.iter() {
+                state.resource_change[resource.id] -=
+                    state.player.resources[resource.id] * resource.decay;
+            }
+            Ok(())
+        })?;
+        let events: Vec<&GlobalEvent> = self.world.active_events().collect();
+        for event in events {
+            self.attributed(EffectSource::Event(event.id), |state| {
+                for (resource, &upkeep) in &event.upkeep {
+                    state.resource_change[resource] -= upkeep;
+                }
+                Ok(())
+            })?;
+        }
+
+        let results: Vec<String> = RESOURCES
+            .iter()
+            .map(|res| format!("{} {:+.2}", res.name(), self.resource_change[res.id]))
+            .collect();
+        narrate!(self, "simulation.results", results = results.join(", "));
+        for contribution in &self.attribution {
+            narrate!(self, "simulation.contribution", contribution = contribution);
+        }
+
+        for resource in RESOURCES.iter() {
+            self.player.resources[resource.id] += self.resource_change[resource.id];
+        }
+        self.resource_change = ResourceMap::default();
+
+        if self.steps_world {
+            self.world.step(&mut self.rng);
         }
 
         self.time_passed += 1;
+        Ok(())
+    }
+
+    /// Применяет эффект и записывает в `attribution`, как он изменил `resource_change`.
+    /// Ошибка эффекта без контекста получает контекст текущего интервала.
+    fn attributed(
+        &mut self,
+        source: EffectSource,
+        effect: impl FnOnce(&mut GameState) -> Result<(), SimError>,
+    ) -> Result<(), SimError> {
+        let before = self.resource_change.clone();
+        effect(self).map_err(|error| match error.context {
+            Some(_) => error,
+            None => self.error(error.kind),
+        })?;
+        let after = self.resource_change.clone();
+
+        if let Some((resource, _)) = after.iter().find(|(_, change)| !change.is_finite()) {
+            return Err(self.error(SimErrorKind::NonFiniteChange { source, resource }));
+        }
+
+        if before != after {
+            self.attribution.push(EffectContribution {
+                source,
+                before,
+                after,
+            });
+        }
+        Ok(())
     }
 }
@@ -1,12 +1,21 @@
-use crate::{definitions::{Action, GameState, Occupation, Strategy}, locations::random_location};
+use crate::{
+    definitions::{Action, GameState, ResourceMap, Strategy},
+    i18n::{text, tr},
+    ids::ResourceId,
+    locations::Location,
+    occupations::Occupation,
+    resources::RESOURCES,
+    trade::{MarketView, TradeAction},
+    travel::next_hop,
+};
 use rand::seq::IteratorRandom;
-use strum::IntoEnumIterator;
+use std::{collections::HashMap, fmt};
 
 pub struct ApatheticStrategy;
 
 impl Strategy for ApatheticStrategy {
     fn name(&self) -> &'static str {
-        "Апатичная стратегия"
+        text("strategy.apathetic")
     }
 
     fn take_action(&self, _state: &mut GameState) -> Action {
@@ -21,15 +30,197 @@ pub struct RandomStrategy;
 
 impl Strategy for RandomStrategy {
     fn name(&self) -> &'static str {
-        "Случайная стратегия"
+        text("strategy.random")
     }
 
     fn take_action(&self, state: &mut GameState) -> Action {
-        let destination = Some(random_location(&mut state.rng));
-        let occupation = Occupation::iter().choose(&mut state.rng);
-        Action {
-            destination,
-            occupation,
+        state
+            .legal_actions()
+            .into_iter()
+            .filter(|action| action.occupation.is_some())
+            .choose(&mut state.rng)
+            .unwrap_or(Action {
+                destination: None,
+                occupation: None,
+            })
+    }
+}
+
+/// Геном параметризованной стратегии на правилах.
+///
+/// Для каждого ресурса задаётся порог и локация. Если ресурс опустился
+/// ниже порога, Игрок отправляется в соответствующую локацию и
+/// предаётся занятию, восполняющему этот ресурс.
+#[derive(Clone)]
+pub struct RuleGenome {
+    /// Порог срабатывания правила для каждого ресурса.
+    pub thresholds: ResourceMap,
+
+    /// Локация, в которую Игрок отправляется восполнять каждый ресурс.
+    pub locations: HashMap<ResourceId, &'static Location>,
+}
+
+impl RuleGenome {
+    /// Выбирает действие по правилам генома.
+    /// Если сработало несколько правил, побеждает ресурс, сильнее всего
+    /// опустившийся ниже своего порога. Если нужная локация не соседствует
+    /// с текущей, Игрок делает шаг по кратчайшему пути к ней.
+    pub fn decide(&self, state: &GameState) -> Action {
+        let resources = &state.player.resources;
+        let urgent = RESOURCES
+            .iter()
+            .map(|res| res.id)
+            .filter(|&res| resources[res] < self.thresholds[res])
+            .min_by(|&a, &b| {
+                let deficit_a = resources[a] - self.thresholds[a];
+                let deficit_b = resources[b] - self.thresholds[b];
+                deficit_a.total_cmp(&deficit_b)
+            });
+
+        match urgent {
+            Some(res) => Action {
+                destination: self
+                    .locations
+                    .get(&res)
+                    .and_then(|&location| next_hop(state.location, location)),
+                occupation: Occupation::producing(res),
+            },
+            None => Action {
+                destination: None,
+                occupation: None,
+            },
+        }
+    }
+}
+
+impl fmt::Display for RuleGenome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for res in RESOURCES.iter() {
+            writeln!(
+                f,
+                "{}",
+                tr!(
+                    "genome.rule",
+                    resource = res.name(),
+                    threshold = self.thresholds[res.id],
+                    location = self
+                        .locations
+                        .get(&res.id)
+                        .map_or("-", |location| location.name()),
+                    occupation = Occupation::producing(res.id)
+                        .map_or(text("genome.nothing"), |occupation| occupation.name())
+                )
+            )?;
+        }
+        write!(f, "{}", text("genome.otherwise"))
+    }
+}
+
+pub struct RuleStrategy {
+    pub genome: RuleGenome,
+}
+
+impl Strategy for RuleStrategy {
+    fn name(&self) -> &'static str {
+        text("strategy.rules")
+    }
+
+    fn take_action(&self, state: &mut GameState) -> Action {
+        let action = self.genome.decide(state);
+        if state.validate_action(&action).is_ok() {
+            return action;
+        }
+
+        // Если добраться до нужной локации или заняться там нужным делом
+        // нельзя, Игрок пробует заняться тем же на месте, а если не выходит
+        // и это - не делает ничего.
+        let in_place = Action {
+            destination: None,
+            ..action
+        };
+        if state.validate_action(&in_place).is_ok() {
+            in_place
+        } else {
+            Action {
+                destination: None,
+                occupation: None,
+            }
+        }
+    }
+}
+
+/// Стратегия, на каждом шаге совершающая одно и то же действие.
+pub struct FixedActionStrategy {
+    pub action: Action,
+}
+
+impl Strategy for FixedActionStrategy {
+    fn name(&self) -> &'static str {
+        text("strategy.fixed")
+    }
+
+    fn take_action(&self, _state: &mut GameState) -> Action {
+        self.action
+    }
+}
+
+/// Стратегия, которая действует как `inner`, а излишки самого обильного
+/// ресурса обменивает у других жителей на самый скудный.
+pub struct TradingStrategy {
+    pub inner: Box<dyn Strategy>,
+
+    /// Ресурс, которого меньше этого запаса, Игрок хочет купить,
+    /// а всё, что сверх него, готов продать.
+    pub reserve: f64,
+
+    /// Сколько единиц излишка Игрок готов отдать за единицу недостающего ресурса.
+    pub max_price: f64,
+}
+
+impl Strategy for TradingStrategy {
+    fn name(&self) -> &'static str {
+        text("strategy.trading")
+    }
+
+    fn take_action(&self, state: &mut GameState) -> Action {
+        self.inner.take_action(state)
+    }
+
+    fn trade(&self, state: &GameState, market: &MarketView) -> Vec<TradeAction> {
+        let resources = &state.player.resources;
+        let by_amount = |a: &ResourceId, b: &ResourceId| resources[*a].total_cmp(&resources[*b]);
+        let ids = || RESOURCES.iter().map(|res| res.id);
+        let (Some(scarce), Some(rich)) = (ids().min_by(by_amount), ids().max_by(by_amount)) else {
+            return Vec::new();
+        };
+
+        let mut actions: Vec<TradeAction> = market
+            .my_offers()
+            .map(|offer| TradeAction::Cancel { offer: offer.id })
+            .collect();
+
+        let surplus = resources[rich] - self.reserve;
+        let need = self.reserve - resources[scarce];
+        if surplus <= 0.0 || need <= 0.0 {
+            return actions;
+        }
+
+        match market
+            .best_offer(scarce, rich)
+            .filter(|offer| offer.price <= self.max_price)
+        {
+            Some(offer) => actions.push(TradeAction::Accept {
+                offer: offer.id,
+                amount: need.min(surplus / offer.price),
+            }),
+            // Никто не продаёт нужное - Игрок сам предлагает обмен один к одному.
+            None => actions.push(TradeAction::Offer {
+                sell: rich,
+                amount: surplus.min(need),
+                buy: scarce,
+                price: 1.0,
+            }),
         }
+        actions
     }
 }
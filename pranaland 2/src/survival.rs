@@ -0,0 +1,279 @@
+use crate::{
+    batch::GameOutcome,
+    i18n::text,
+    ids::{IdMap, ResourceId},
+    resources::RESOURCES,
+};
+use std::{borrow::Cow, fmt::Write as _, fs, io, path::Path};
+
+/// Цвета линий на графиках выживаемости.
+const PALETTE: [&str; 6] = [
+    "#1f77b4", "#d62728", "#2ca02c", "#ff7f0e", "#9467bd", "#8c564b",
+];
+
+/// Точка кривой выживаемости Каплана-Мейера.
+#[derive(Debug, Clone, Copy)]
+pub struct SurvivalPoint {
+    /// Номер временного интервала (начиная с 1).
+    pub step: usize,
+
+    /// Сколько Игроков было живо в начале интервала.
+    pub at_risk: usize,
+
+    /// Сколько Игроков погибло за интервал.
+    pub deaths: usize,
+
+    /// Сколько Игроков выбыло из наблюдения живыми в конце интервала
+    /// (дожили до горизонта симуляции).
+    pub censored: usize,
+
+    /// Оценка доли выживших к концу интервала.
+    pub survival: f64,
+
+    /// Доля погибших среди живых в начале интервала.
+    pub hazard: f64,
+}
+
+/// Кривая выживаемости для одной пары "нация × стратегия".
+pub struct SurvivalCurve {
+    /// Нация.
+    pub nation: &'static str,
+
+    /// Стратегия.
+    pub strategy: &'static str,
+
+    pub points: Vec<SurvivalPoint>,
+
+    /// Сколько Игроков погибло от нехватки каждого ресурса.
+    pub death_causes: IdMap<ResourceId, usize>,
+}
+
+impl SurvivalCurve {
+    /// Строит оценку Каплана-Мейера по итогам пакетного прогона с горизонтом
+    /// `horizon`. Игроки, дожившие до горизонта (в том числе погибшие позже
+    /// него), считаются цензурированными на горизонте.
+    pub fn new(
+        nation: &'static str,
+        strategy: &'static str,
+        outcomes: &[GameOutcome],
+        horizon: usize,
+    ) -> Self {
+        let mut deaths = vec![0; horizon + 1];
+        let mut censored = vec![0; horizon + 1];
+        let mut death_causes = IdMap::default();
+
+        for outcome in outcomes {
+            let step = outcome.steps_survived.min(horizon);
+            match outcome.death_cause {
+                Some(cause) if !outcome.survived && outcome.steps_survived <= horizon => {
+                    deaths[step] += 1;
+                    death_causes[cause] += 1;
+                }
+                _ => censored[step] += 1,
+            }
+        }
+
+        let mut at_risk = outcomes.len();
+        let mut survival = 1.0;
+        let points = (1..=horizon)
+            .map(|step| {
+                let hazard = if at_risk == 0 {
+                    0.0
+                } else {
+                    deaths[step] as f64 / at_risk as f64
+                };
+                survival *= 1.0 - hazard;
+
+                let point = SurvivalPoint {
+                    step,
+                    at_risk,
+                    deaths: deaths[step],
+                    censored: censored[step],
+                    survival,
+                    hazard,
+                };
+                at_risk -= deaths[step] + censored[step];
+                point
+            })
+            .collect();
+
+        Self {
+            nation,
+            strategy,
+            points,
+            death_causes,
+        }
+    }
+
+    /// Оценка доли Игроков, доживших до горизонта.
+    pub fn final_survival(&self) -> f64 {
+        self.points.last().map_or(1.0, |p| p.survival)
+    }
+}
+
+/// Сохраняет кривые выживаемости в CSV, по строке на каждую точку.
+pub fn write_csv(curves: &[SurvivalCurve], path: &Path) -> io::Result<()> {
+    let mut csv = String::from("nation,strategy,step,at_risk,deaths,censored,survival,hazard\n");
+    for curve in curves {
+        for p in &curve.points {
+            writeln!(
+                csv,
+                "{},{},{},{},{},{},{:.6},{:.6}",
+                csv_field(curve.nation),
+                csv_field(curve.strategy),
+                p.step, p.at_risk, p.deaths, p.censored, p.survival, p.hazard
+            )
+            .unwrap();
+        }
+    }
+    fs::write(path, csv)
+}
+
+/// Сохраняет в CSV количество смертей от нехватки каждого ресурса.
+pub fn write_death_causes_csv(curves: &[SurvivalCurve], path: &Path) -> io::Result<()> {
+    let mut csv = String::from("nation,strategy,resource,deaths\n");
+    for curve in curves {
+        for res in RESOURCES.iter() {
+            writeln!(
+                csv,
+                "{},{},{},{}",
+                csv_field(curve.nation),
+                csv_field(curve.strategy),
+                res.id,
+                curve.death_causes[res.id]
+            )
+            .unwrap();
+        }
+    }
+    fs::write(path, csv)
+}
+
+/// Рисует кривые выживаемости на одном графике и сохраняет его в
+/// самодостаточный SVG-файл.
+pub fn write_svg(title: &str, curves: &[SurvivalCurve], path: &Path) -> io::Result<()> {
+    const WIDTH: f64 = 720.0;
+    const HEIGHT: f64 = 420.0;
+    const LEFT: f64 = 60.0;
+    const RIGHT: f64 = 200.0;
+    const TOP: f64 = 40.0;
+    const BOTTOM: f64 = 50.0;
+
+    let plot_width = WIDTH - LEFT - RIGHT;
+    let plot_height = HEIGHT - TOP - BOTTOM;
+    let horizon = curves
+        .iter()
+        .map(|c| c.points.len())
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let x = |step: usize| LEFT + plot_width * step as f64 / horizon as f64;
+    let y = |survival: f64| TOP + plot_height * (1.0 - survival);
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" font-family="sans-serif" font-size="12">"#
+    )
+    .unwrap();
+    writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#).unwrap();
+    writeln!(
+        svg,
+        r#"<text x="{}" y="24" text-anchor="middle" font-size="16">{}</text>"#,
+        LEFT + plot_width / 2.0,
+        escape(title)
+    )
+    .unwrap();
+
+    // Сетка и подписи осей.
+    for i in 0..=4 {
+        let fraction = i as f64 / 4.0;
+        writeln!(
+            svg,
+            r##"<line x1="{LEFT}" y1="{0}" x2="{1}" y2="{0}" stroke="#ddd"/><text x="{2}" y="{3}" text-anchor="end">{fraction:.2}</text>"##,
+            y(fraction),
+            LEFT + plot_width,
+            LEFT - 6.0,
+            y(fraction) + 4.0,
+        )
+        .unwrap();
+
+        let step = horizon * i / 4;
+        writeln!(
+            svg,
+            r#"<text x="{}" y="{}" text-anchor="middle">{step}</text>"#,
+            x(step),
+            TOP + plot_height + 18.0
+        )
+        .unwrap();
+    }
+    writeln!(
+        svg,
+        r#"<rect x="{LEFT}" y="{TOP}" width="{plot_width}" height="{plot_height}" fill="none" stroke="black"/>"#
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+        LEFT + plot_width / 2.0,
+        HEIGHT - 12.0,
+        escape(text("survival.svg.step"))
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<text x="16" y="{0}" text-anchor="middle" transform="rotate(-90 16 {0})">{1}</text>"#,
+        TOP + plot_height / 2.0,
+        escape(text("survival.svg.survival"))
+    )
+    .unwrap();
+
+    // Ступенчатые кривые и легенда.
+    for (i, curve) in curves.iter().enumerate() {
+        let color = PALETTE[i % PALETTE.len()];
+
+        let mut points = format!("{},{}", x(0), y(1.0));
+        let mut previous = 1.0;
+        for p in &curve.points {
+            write!(points, " {},{} {},{}", x(p.step), y(previous), x(p.step), y(p.survival)).unwrap();
+            previous = p.survival;
+        }
+        writeln!(
+            svg,
+            r#"<polyline points="{points}" fill="none" stroke="{color}" stroke-width="2"/>"#
+        )
+        .unwrap();
+
+        let legend_y = TOP + 10.0 + 20.0 * i as f64;
+        writeln!(
+            svg,
+            r#"<line x1="{0}" y1="{1}" x2="{2}" y2="{1}" stroke="{color}" stroke-width="2"/><text x="{3}" y="{4}">{5}</text>"#,
+            LEFT + plot_width + 12.0,
+            legend_y,
+            LEFT + plot_width + 32.0,
+            LEFT + plot_width + 38.0,
+            legend_y + 4.0,
+            escape(curve.strategy)
+        )
+        .unwrap();
+    }
+
+    writeln!(svg, "</svg>").unwrap();
+    fs::write(path, svg)
+}
+
+/// Поле CSV: текст с запятыми, кавычками или переводами строк берётся
+/// в кавычки, а кавычки внутри него удваиваются.
+fn csv_field(text: &str) -> Cow<'_, str> {
+    if text.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", text.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
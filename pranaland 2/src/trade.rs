@@ -0,0 +1,451 @@
+use crate::{
+    definitions::Player,
+    ids::{LocationId, ResourceId},
+    i18n::{text, tr},
+    locations::{Location, LOCATIONS},
+    multiplayer::World,
+};
+use rand::seq::SliceRandom;
+use std::{
+    collections::HashMap,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+};
+
+/// Предложение обмена, выставленное жителем на рынке своей локации.
+#[derive(Debug, Clone, Copy)]
+pub struct Offer {
+    pub id: usize,
+
+    /// Номер выставившего предложение жителя в `World::inhabitants`.
+    pub owner: usize,
+
+    /// Ресурс, который житель отдаёт.
+    pub sell: ResourceId,
+
+    /// Сколько ресурса `sell` ещё можно купить по этому предложению.
+    pub amount: f64,
+
+    /// Ресурс, который житель хочет получить взамен.
+    pub buy: ResourceId,
+
+    /// Сколько единиц ресурса `buy` житель хочет за единицу `sell`.
+    pub price: f64,
+}
+
+/// Участие жителя в торговле за один временной интервал.
+#[derive(Debug, Clone, Copy)]
+pub enum TradeAction {
+    /// Выставить предложение отдать `amount` ресурса `sell`, получая по `price`
+    /// единиц `buy` за каждую. Встречные предложения, согласные на такой курс,
+    /// исполняются сразу, а остаток ждёт на рынке, пока его не примут.
+    /// Новое предложение заменяет прежнее предложение жителя с той же парой ресурсов.
+    Offer {
+        sell: ResourceId,
+        amount: f64,
+        buy: ResourceId,
+        price: f64,
+    },
+
+    /// Принять предложение `offer`, купив `amount` его ресурса
+    /// (или всё, что осталось, если осталось меньше).
+    Accept { offer: usize, amount: f64 },
+
+    /// Снять своё предложение `offer` с рынка.
+    Cancel { offer: usize },
+}
+
+/// Причина, по которой торговое действие не может быть совершено.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TradeError {
+    /// Ресурс нельзя обменять на него же.
+    SameResource,
+
+    /// Количество и цена должны быть конечными положительными числами.
+    NonPositive,
+
+    /// На рынке локации нет такого предложения (или оно чужое, если его снимают).
+    UnknownOffer(usize),
+
+    /// Житель пытается принять собственное предложение.
+    OwnOffer,
+
+    /// Локации нет в содержимом игры, и рынка в ней нет.
+    NoMarket(LocationId),
+
+    /// После обмена ресурса у участника не осталось бы больше порога гибели.
+    Insufficient {
+        resource: ResourceId,
+        required: f64,
+        available: f64,
+    },
+}
+
+/// Отклонённое торговое действие и причина отказа.
+pub type Rejection = (TradeAction, TradeError);
+
+/// Состоявшийся обмен.
+#[derive(Clone, Copy)]
+pub struct Trade {
+    pub time: usize,
+    pub location: &'static Location,
+
+    /// Житель, отдавший `amount` ресурса `sold`.
+    pub seller: usize,
+
+    /// Житель, заплативший за него по `price` единиц ресурса `paid`.
+    pub buyer: usize,
+
+    pub sold: ResourceId,
+    pub amount: f64,
+    pub paid: ResourceId,
+    pub price: f64,
+}
+
+/// Биржевая книга одной локации - все ждущие ответа предложения.
+#[derive(Debug, Clone, Default)]
+pub struct OrderBook {
+    pub offers: Vec<Offer>,
+}
+
+impl OrderBook {
+    /// Самое выгодное для покупателя предложение отдать `sell` за `buy`,
+    /// не считая предложений жителя `except`.
+    pub fn best_offer(&self, sell: ResourceId, buy: ResourceId, except: usize) -> Option<&Offer> {
+        self.offers
+            .iter()
+            .filter(|offer| offer.sell == sell && offer.buy == buy && offer.owner != except)
+            .min_by(|a, b| a.price.total_cmp(&b.price))
+    }
+}
+
+/// Рынки всех локаций и история обменов.
+#[derive(Clone)]
+pub struct Market {
+    /// Биржевая книга для каждой локации (в порядке `LOCATIONS`).
+    pub books: Vec<OrderBook>,
+
+    pub trades: Vec<Trade>,
+
+    /// Торговые действия каждого жителя (по номеру в `World::inhabitants`),
+    /// отклонённые на его последних торгах, с причинами. Житель видит их
+    /// в `MarketView::rejected` на следующих торгах.
+    pub rejected: HashMap<usize, Vec<Rejection>>,
+
+    next_id: usize,
+}
+
+impl Market {
+    pub fn new() -> Self {
+        Self {
+            books: vec![OrderBook::default(); LOCATIONS.len()],
+            trades: Vec::new(),
+            rejected: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Курс последнего обмена `sold` на `paid` в локации `location`:
+    /// сколько единиц `paid` отдали за единицу `sold`.
+    pub fn last_price(
+        &self,
+        location: &'static Location,
+        sold: ResourceId,
+        paid: ResourceId,
+    ) -> Option<f64> {
+        self.trades
+            .iter()
+            .rev()
+            .find(|trade| {
+                trade.location == location && trade.sold == sold && trade.paid == paid
+            })
+            .map(|trade| trade.price)
+    }
+}
+
+impl Default for Market {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// То, что видит житель, решая, как торговать.
+pub struct MarketView<'a> {
+    /// Биржевая книга локации, в которой находится житель.
+    pub book: &'a OrderBook,
+
+    /// Номер жителя в `World::inhabitants`, чтобы отличать свои предложения от чужих.
+    pub me: usize,
+
+    /// Действия жителя, отклонённые на его прошлых торгах, и причины отказа.
+    pub rejected: &'a [Rejection],
+}
+
+impl MarketView<'_> {
+    /// Самое выгодное чужое предложение отдать `sell` за `buy`.
+    pub fn best_offer(&self, sell: ResourceId, buy: ResourceId) -> Option<&Offer> {
+        self.book.best_offer(sell, buy, self.me)
+    }
+
+    /// Собственные предложения жителя.
+    pub fn my_offers(&self) -> impl Iterator<Item = &Offer> {
+        self.book.offers.iter().filter(|offer| offer.owner == self.me)
+    }
+}
+
+/// Проверяет, что у Игрока останется ресурс, после того как он отдаст `required`.
+/// Годится ли `value` как количество или цена: NaN и бесконечность -
+/// нет, иначе они разошлись бы по ресурсам обеих сторон сделки.
+fn is_positive(value: f64) -> bool {
+    value.is_finite() && value > 0.0
+}
+
+fn check_affordable(player: &Player, resource: ResourceId, required: f64) -> Result<(), TradeError> {
+    let available = player.resources[resource];
+    if player.margin(resource) <= required {
+        Err(TradeError::Insufficient {
+            resource,
+            required,
+            available,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+impl World {
+    /// Торги за один временной интервал. Жители в случайном порядке
+    /// выставляют, принимают и снимают предложения на рынке своей локации.
+    /// Жители в пути не торгуют.
+    pub(crate) fn trade(&mut self) {
+        self.remove_stale_offers();
+
+        let mut order: Vec<usize> = (0..self.inhabitants.len()).collect();
+        order.shuffle(&mut self.rng);
+
+        for me in order {
+            let state = &self.inhabitants[me].state;
+            if state.player.is_dead() || state.transit.is_some() {
+                continue;
+            }
+
+            let location = state.location;
+            // Вне содержимого игры рынков нет: такой житель не торгует.
+            let Ok(book) = location.index() else {
+                continue;
+            };
+            let rejected = self.market.rejected.remove(&me).unwrap_or_default();
+            let view = MarketView {
+                book: &self.market.books[book],
+                me,
+                rejected: &rejected,
+            };
+            let strategy = &self.inhabitants[me].strategy;
+            // Запаниковавшая стратегия на этом интервале просто не торгует.
+            let actions = panic::catch_unwind(AssertUnwindSafe(|| strategy.trade(state, &view)))
+                .unwrap_or_default();
+
+            // Как и с недопустимым действием, неудачная сделка просто не
+            // совершается, но стратегия узнает о ней на следующих торгах.
+            let failures: Vec<Rejection> = actions
+                .into_iter()
+                .filter_map(|action| {
+                    let error = self.perform_trade(me, location, action).err()?;
+                    Some((action, error))
+                })
+                .collect();
+            if !failures.is_empty() {
+                self.market.rejected.insert(me, failures);
+            }
+        }
+    }
+
+    /// Совершает торговое действие `action` жителя `me` в локации `location`.
+    pub fn perform_trade(
+        &mut self,
+        me: usize,
+        location: &'static Location,
+        action: TradeAction,
+    ) -> Result<(), TradeError> {
+        let book = location
+            .index()
+            .map_err(|_| TradeError::NoMarket(location.id))?;
+        match action {
+            TradeAction::Offer {
+                sell,
+                amount,
+                buy,
+                price,
+            } => {
+                if sell == buy {
+                    return Err(TradeError::SameResource);
+                }
+                if !is_positive(amount) || !is_positive(price) {
+                    return Err(TradeError::NonPositive);
+                }
+                check_affordable(&self.inhabitants[me].state.player, sell, amount)?;
+
+                self.market.books[book]
+                    .offers
+                    .retain(|offer| !(offer.owner == me && offer.sell == sell && offer.buy == buy));
+
+                // Встречные предложения, согласные отдать `buy` не дороже,
+                // чем за 1 / price единиц `sell`, исполняются по их цене.
+                let mut remaining = amount;
+                while remaining > 0.0 {
+                    let Some((counter, counter_price)) = self.market.books[book]
+                        .best_offer(buy, sell, me)
+                        .filter(|counter| counter.price * price <= 1.0)
+                        .map(|counter| (counter.id, counter.price))
+                    else {
+                        break;
+                    };
+                    // Покупаем встречный `buy`, расплачиваясь своим `sell`.
+                    let wanted = remaining / counter_price;
+                    match self.execute(me, location, book, counter, wanted) {
+                        Ok(bought) => remaining -= bought * counter_price,
+                        Err(_) => break,
+                    }
+                }
+
+                if remaining > 0.0 {
+                    let id = self.market.next_id;
+                    self.market.next_id += 1;
+                    self.market.books[book].offers.push(Offer {
+                        id,
+                        owner: me,
+                        sell,
+                        amount: remaining,
+                        buy,
+                        price,
+                    });
+                }
+                Ok(())
+            }
+            TradeAction::Accept { offer, amount } => {
+                if !is_positive(amount) {
+                    return Err(TradeError::NonPositive);
+                }
+                let owner = self.market.books[book]
+                    .offers
+                    .iter()
+                    .find(|o| o.id == offer)
+                    .ok_or(TradeError::UnknownOffer(offer))?
+                    .owner;
+                if owner == me {
+                    return Err(TradeError::OwnOffer);
+                }
+                self.execute(me, location, book, offer, amount).map(|_| ())
+            }
+            TradeAction::Cancel { offer } => {
+                let offers = &mut self.market.books[book].offers;
+                let len = offers.len();
+                offers.retain(|o| !(o.id == offer && o.owner == me));
+                if offers.len() == len {
+                    Err(TradeError::UnknownOffer(offer))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Номер предложения `id` в книге `book`.
+    fn find_offer(&self, book: usize, id: usize) -> Result<usize, TradeError> {
+        self.market.books[book]
+            .offers
+            .iter()
+            .position(|offer| offer.id == id)
+            .ok_or(TradeError::UnknownOffer(id))
+    }
+
+    /// Житель `buyer` покупает до `amount` ресурса по предложению `id`
+    /// на рынке `book` локации `location`. Возвращает, сколько удалось купить.
+    fn execute(
+        &mut self,
+        buyer: usize,
+        location: &'static Location,
+        book: usize,
+        id: usize,
+        amount: f64,
+    ) -> Result<f64, TradeError> {
+        let index = self.find_offer(book, id)?;
+        let offer = self.market.books[book].offers[index];
+        let amount = amount.min(offer.amount);
+        let payment = amount * offer.price;
+
+        if let Err(err) = check_affordable(&self.inhabitants[offer.owner].state.player, offer.sell, amount) {
+            // Продавец больше не может выполнить предложение.
+            self.market.books[book].offers.retain(|o| o.id != id);
+            return Err(err);
+        }
+        check_affordable(&self.inhabitants[buyer].state.player, offer.buy, payment)?;
+
+        let seller = &mut self.inhabitants[offer.owner].state.player.resources;
+        seller[offer.sell] -= amount;
+        seller[offer.buy] += payment;
+        let customer = &mut self.inhabitants[buyer].state.player.resources;
+        customer[offer.sell] += amount;
+        customer[offer.buy] -= payment;
+
+        let offers = &mut self.market.books[book].offers;
+        offers[index].amount -= amount;
+        if offers[index].amount <= f64::EPSILON {
+            offers.remove(index);
+        }
+
+        self.market.trades.push(Trade {
+            time: self.time_passed,
+            location,
+            seller: offer.owner,
+            buyer,
+            sold: offer.sell,
+            amount,
+            paid: offer.buy,
+            price: offer.price,
+        });
+        Ok(amount)
+    }
+
+    /// Снимает предложения погибших жителей и тех, кто покинул локацию.
+    fn remove_stale_offers(&mut self) {
+        for (i, book) in self.market.books.iter_mut().enumerate() {
+            book.offers.retain(|offer| {
+                let state = &self.inhabitants[offer.owner].state;
+                state.player.is_alive()
+                    && state.transit.is_none()
+                    && state.location.index().is_ok_and(|index| index == i)
+            });
+        }
+    }
+}
+
+impl fmt::Display for TradeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TradeError::SameResource => write!(f, "{}", text("trade.same_resource")),
+            TradeError::NonPositive => write!(f, "{}", text("trade.non_positive")),
+            TradeError::UnknownOffer(id) => write!(f, "{}", tr!("trade.unknown_offer", id = id)),
+            TradeError::OwnOffer => write!(f, "{}", text("trade.own_offer")),
+            TradeError::NoMarket(location) => {
+                write!(f, "{}", tr!("trade.no_market", location = location.name()))
+            }
+            TradeError::Insufficient {
+                resource,
+                required,
+                available,
+            } => write!(
+                f,
+                "{}",
+                tr!(
+                    "trade.insufficient",
+                    resource = resource.name(),
+                    required = required,
+                    available = available
+                )
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TradeError {}
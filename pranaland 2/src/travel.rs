@@ -0,0 +1,153 @@
+use crate::{
+    definitions::ResourceMap,
+    ids::LocationId,
+    locations::{Location, LOCATIONS},
+    registry::content,
+    resources::{amounts, HEALTH, MONEY},
+};
+use lazy_static::lazy_static;
+use std::collections::VecDeque;
+
+/// Путь из одной локации в другую.
+/// Маршруты односторонние: обратный путь, если он есть, описывается
+/// отдельным маршрутом.
+pub struct Route {
+    pub from: &'static Location,
+    pub to: &'static Location,
+
+    /// Сколько каждого ресурса стоит дорога.
+    pub cost: ResourceMap,
+
+    /// Сколько временных интервалов Игрок проводит в пути, прежде чем
+    /// прибыть на место. В пути Игрок ничем не занимается.
+    pub extra_intervals: usize,
+}
+
+/// Описание маршрута между биомами в наборе содержимого.
+pub struct RouteDef {
+    pub from: LocationId,
+    pub to: LocationId,
+    pub cost: ResourceMap,
+    pub extra_intervals: usize,
+}
+
+/// Маршрут между биомами в обе стороны.
+pub fn both_ways(a: LocationId, b: LocationId, cost: ResourceMap, extra_intervals: usize) -> [RouteDef; 2] {
+    [
+        RouteDef { from: a, to: b, cost: cost.clone(), extra_intervals },
+        RouteDef { from: b, to: a, cost, extra_intervals },
+    ]
+}
+
+/// Маршруты между биомами базового набора содержимого.
+pub fn base_routes() -> Vec<RouteDef> {
+    let mut routes = Vec::new();
+    routes.extend(both_ways(
+        LocationId("dolbesburg"),
+        LocationId("punta-pelikana"),
+        amounts(&[(MONEY, 1.0)]),
+        0,
+    ));
+    routes.extend(both_ways(
+        LocationId("kuramariby"),
+        LocationId("shrinavas"),
+        amounts(&[(MONEY, 1.0)]),
+        1,
+    ));
+
+    // Паломничество из Воркленда в Праналенд: туда можно добраться напрямую,
+    // а обратно - только через Бичленд.
+    routes.push(RouteDef {
+        from: LocationId("balbesburg"),
+        to: LocationId("hare-kirishi"),
+        cost: amounts(&[(HEALTH, 1.0)]),
+        extra_intervals: 1,
+    });
+
+    routes
+}
+
+lazy_static! {
+    /// Все маршруты. Локации одного биома соседствуют, и дорога между ними
+    /// бесплатна, а биомы связаны лишь несколькими платными маршрутами
+    /// из установленного содержимого игры (`content()`).
+    pub static ref TRAVEL_ROUTES: Vec<Route> = {
+        let mut routes: Vec<Route> = LOCATIONS
+            .iter()
+            .flat_map(|from| {
+                LOCATIONS
+                    .iter()
+                    .filter(move |to| to.biome == from.biome && *to != from)
+                    .map(move |to| Route {
+                        from,
+                        to,
+                        cost: ResourceMap::default(),
+                        extra_intervals: 0,
+                    })
+            })
+            .collect();
+
+        // Концы маршрутов проверены при загрузке содержимого.
+        let defs = content().map(|content| content.routes.as_slice()).unwrap_or_default();
+        routes.extend(defs.iter().filter_map(|route| {
+            Some(Route {
+                from: route.from.try_get().ok()?,
+                to: route.to.try_get().ok()?,
+                cost: route.cost.clone(),
+                extra_intervals: route.extra_intervals,
+            })
+        }));
+
+        routes
+    };
+}
+
+/// Маршрут из `from` в `to`, если он существует.
+pub fn find_route(from: &'static Location, to: &'static Location) -> Option<&'static Route> {
+    TRAVEL_ROUTES
+        .iter()
+        .find(|route| route.from == from && route.to == to)
+}
+
+/// Все маршруты, начинающиеся в `from`.
+pub fn routes_from(from: &'static Location) -> impl Iterator<Item = &'static Route> {
+    TRAVEL_ROUTES
+        .iter()
+        .filter(move |route| route.from == from)
+}
+
+/// Первая локация на кратчайшем (по числу переходов) пути из `from` в `to`,
+/// либо `None`, если `to` недостижима (в том числе если её нет в `LOCATIONS`)
+/// или совпадает с `from`.
+pub fn next_hop(from: &'static Location, to: &'static Location) -> Option<&'static Location> {
+    if from == to {
+        return None;
+    }
+
+    let mut first_hop: Vec<Option<&'static Location>> = vec![None; LOCATIONS.len()];
+    let mut queue = VecDeque::new();
+
+    for route in routes_from(from) {
+        let next = route.to.index().ok()?;
+        if first_hop[next].is_none() {
+            first_hop[next] = Some(route.to);
+            queue.push_back(route.to);
+        }
+    }
+
+    while let Some(current) = queue.pop_front() {
+        let hop = first_hop[current.index().ok()?];
+        if current == to {
+            return hop;
+        }
+        for route in routes_from(current) {
+            let next = route.to.index().ok()?;
+            if first_hop[next].is_none() && route.to != from {
+                first_hop[next] = hop;
+                queue.push_back(route.to);
+            }
+        }
+    }
+
+    None
+}
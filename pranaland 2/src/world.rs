@@ -0,0 +1,196 @@
+use crate::{
+    calendar::{Calendar, GlobalEvent},
+    definitions::{FaunaMap, SimError},
+    fauna::FAUNA,
+    ids::{BiomeId, FaunaId, IdMap, ResourceId},
+    locations::{Location, LOCATIONS},
+};
+use rand::{seq::SliceRandom, Rng};
+
+/// Параметры изменения численности фауны в локации со временем.
+#[derive(Debug, Clone, Copy)]
+pub struct FaunaDynamics {
+    /// На сколько уменьшается популяция фауны за каждый временной интервал,
+    /// когда Игрок предаётся занятию, для которого она нужна.
+    pub depletion: f64,
+
+    /// Скорость логистического восстановления популяции за временной интервал.
+    pub regrowth: f64,
+
+    /// Ёмкость среды - доля начальной численности (`Location::fauna`),
+    /// до которой восстанавливается популяция. Начальную численность
+    /// популяция не превышает никогда, в том числе из-за миграции.
+    pub capacity: f64,
+
+    /// Вероятность того, что за временной интервал один представитель каждого
+    /// вида фауны мигрирует в другую локацию с динамикой фауны, где для него
+    /// есть место.
+    pub migration: f64,
+}
+
+impl FaunaDynamics {
+    /// Динамика, при которой фауна понемногу истощается, если её
+    /// эксплуатировать, и восстанавливается, пока Игрок занят чем-то другим.
+    pub const STANDARD: FaunaDynamics = FaunaDynamics {
+        depletion: 0.15,
+        regrowth: 0.2,
+        capacity: 1.0,
+        migration: 0.02,
+    };
+
+    /// Поля, значения которых вне `0.0..=1.0` (все поля - доли или
+    /// вероятности), и их значения.
+    pub fn invalid_fields(&self) -> Vec<(&'static str, f64)> {
+        [
+            ("depletion", self.depletion),
+            ("regrowth", self.regrowth),
+            ("capacity", self.capacity),
+            ("migration", self.migration),
+        ]
+        .into_iter()
+        .filter(|(_, value)| !(0.0..=1.0).contains(value))
+        .collect()
+    }
+}
+
+/// Состояние мира, сохраняющееся между посещениями локаций.
+#[derive(Debug, Clone)]
+pub struct WorldState {
+    /// Популяция каждого вида фауны для каждой локации (в порядке `LOCATIONS`).
+    /// Дробная, чтобы медленное восстановление не терялось при округлении.
+    pub fauna: Vec<IdMap<FaunaId, f64>>,
+
+    /// Мировые часы: время года и глобальные события (`EVENTS`).
+    /// По умолчанию `None`: времена года не меняют доход от фауны,
+    /// события не происходят, и ход игры с базовым содержимым не меняется.
+    pub calendar: Option<Calendar>,
+
+    /// Динамика фауны для локаций, в содержимом которых она не задана.
+    /// По умолчанию `None`: фауна таких локаций неизменна, как в условии
+    /// задачи, и ход игры с базовым содержимым не меняется.
+    pub default_dynamics: Option<FaunaDynamics>,
+}
+
+impl WorldState {
+    /// Мир, в котором численность фауны во всех локациях начальная.
+    pub fn new() -> Self {
+        Self {
+            fauna: LOCATIONS
+                .iter()
+                .map(|loc| {
+                    loc.fauna
+                        .iter()
+                        .map(|(fauna, &count)| (fauna, count as f64))
+                        .collect()
+                })
+                .collect(),
+            calendar: None,
+            default_dynamics: None,
+        }
+    }
+
+    /// Множитель дохода от фауны для занятия, приносящего `resource`,
+    /// в биоме `biome` (см. `Calendar::income_factor`). Без календаря - 1.0.
+    pub fn income_factor(&self, biome: BiomeId, resource: ResourceId) -> f64 {
+        self.calendar
+            .as_ref()
+            .map_or(1.0, |calendar| calendar.income_factor(biome, resource))
+    }
+
+    /// Глобальные события, идущие на текущем временном интервале.
+    pub fn active_events(&self) -> impl Iterator<Item = &'static GlobalEvent> + '_ {
+        self.calendar.iter().flat_map(Calendar::active_events)
+    }
+
+    /// Динамика фауны в локации `location`, если фауна там меняется.
+    pub fn dynamics(&self, location: &Location) -> Option<FaunaDynamics> {
+        location.dynamics.or(self.default_dynamics)
+    }
+
+    /// Количество работающих представителей фауны в локации (популяция,
+    /// округлённая до целого). Ошибка - если локации нет в `LOCATIONS`.
+    pub fn fauna_in(&self, location: &'static Location) -> Result<FaunaMap, SimError> {
+        Ok(self.fauna[location.index()?]
+            .iter()
+            .map(|(fauna, population)| (fauna, population.max(0.0).round() as usize))
+            .collect())
+    }
+
+    /// Игрок эксплуатирует фауну `fauna` в локации `location`.
+    pub fn exploit(&mut self, location: &'static Location, fauna: FaunaId) -> Result<(), SimError> {
+        if let Some(dynamics) = self.dynamics(location) {
+            let population = &mut self.fauna[location.index()?][fauna];
+            *population = (*population - dynamics.depletion).max(0.0);
+        }
+        Ok(())
+    }
+
+    /// Восстановление и миграция фауны за один временной интервал,
+    /// после которых наступает следующий интервал мирового времени.
+    pub fn step(&mut self, rng: &mut impl Rng) {
+        for (i, location) in LOCATIONS.iter().enumerate() {
+            let Some(dynamics) = self.dynamics(location) else {
+                continue;
+            };
+
+            for (fauna, population) in self.fauna[i].iter_mut() {
+                let capacity = location.fauna[fauna] as f64 * dynamics.capacity;
+                if capacity > 0.0 {
+                    let growth = dynamics.regrowth * *population * (1.0 - *population / capacity);
+                    *population = (*population + growth).max(0.0);
+                }
+            }
+        }
+
+        let migrating: Vec<usize> = (0..LOCATIONS.len())
+            .filter(|&i| self.dynamics(&LOCATIONS[i]).is_some())
+            .collect();
+        if migrating.len() >= 2 {
+            self.migrate(&migrating, rng);
+        }
+
+        if let Some(calendar) = &mut self.calendar {
+            calendar.step(rng);
+        }
+    }
+
+    /// Сколько фауны `fauna` может жить в `i`-й локации `LOCATIONS`.
+    fn capacity(&self, i: usize, fauna: FaunaId) -> f64 {
+        let location = &LOCATIONS[i];
+        self.dynamics(location).map_or(0.0, |dynamics| {
+            location.fauna[fauna] as f64 * dynamics.capacity
+        })
+    }
+
+    /// Случайная миграция фауны между локациями `migrating`.
+    fn migrate(&mut self, migrating: &[usize], rng: &mut impl Rng) {
+        for &from in migrating {
+            let migration = self.dynamics(&LOCATIONS[from]).map_or(0.0, |d| d.migration);
+            if migration <= 0.0 {
+                continue;
+            }
+
+            for fauna in FAUNA.iter().map(|fauna| fauna.id) {
+                if self.fauna[from][fauna] >= 1.0 && rng.gen::<f64>() <= migration {
+                    let room: Vec<usize> = migrating
+                        .iter()
+                        .copied()
+                        .filter(|&to| {
+                            to != from && self.fauna[to][fauna] + 1.0 <= self.capacity(to, fauna)
+                        })
+                        .collect();
+                    if let Some(&to) = room.choose(rng) {
+                        self.fauna[from][fauna] -= 1.0;
+                        self.fauna[to][fauna] += 1.0;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Default for WorldState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
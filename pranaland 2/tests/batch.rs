@@ -0,0 +1,49 @@
+//! Пакетный прогон: симуляции, прерванные ошибкой, и зёрна симуляций.
+
+use pranaland::{
+    batch::{run_batch, BatchConfig},
+    definitions::{Action, GameState, SimErrorKind, Strategy},
+    ids::OccupationId,
+    nations::NATIONS,
+};
+
+/// Бездельничает два интервала, а на третьем выбирает несуществующее занятие.
+struct FailingStrategy;
+
+impl Strategy for FailingStrategy {
+    fn name(&self) -> &'static str {
+        "Ломающаяся стратегия"
+    }
+
+    fn take_action(&self, state: &mut GameState) -> Action {
+        Action {
+            destination: None,
+            occupation: (state.time_passed == 2).then_some(OccupationId("sleeping")),
+        }
+    }
+}
+
+#[test]
+fn failed_games_count_as_deaths_at_the_error_step() {
+    let config = BatchConfig {
+        games: 3,
+        seed: u64::MAX - 1,
+        ..Default::default()
+    };
+    let result = run_batch(&FailingStrategy, Some(&NATIONS[0]), &config);
+
+    assert_eq!(result.outcomes.len(), 3);
+    assert_eq!(result.errors.len(), 3);
+    assert!(result
+        .errors
+        .iter()
+        .all(|error| matches!(error.kind, SimErrorKind::IllegalAction(_))));
+    for outcome in &result.outcomes {
+        assert!(outcome.failed);
+        assert!(!outcome.survived);
+        assert_eq!(outcome.death_cause, None);
+        assert_eq!(outcome.steps_survived, 2);
+    }
+    assert_eq!(result.mean_survival(), 2.0);
+    assert_eq!(result.survival_rate(), 0.0);
+}
@@ -0,0 +1,124 @@
+//! Календарь: времена года и глобальные события из наборов содержимого.
+
+use pranaland::{
+    base_pack,
+    calendar::{Calendar, GlobalEvent},
+    definitions::{EffectSource, SimErrorKind},
+    ids::{EventId, ResourceId},
+    install,
+    locations::BIOMES,
+    registry::{ContentError, ContentKind},
+    resources::{amounts, MONEY},
+    strategies::ApatheticStrategy,
+    ContentPack, ContentRegistry, GameState, ResourceMap, RESOURCES,
+};
+use std::sync::Once;
+
+const DROUGHT: EventId = EventId("drought");
+
+/// Мод с засухой, которая начинается сразу, как только может.
+fn drought_pack() -> ContentPack {
+    ContentPack {
+        name: "Засуха",
+        resources: vec![],
+        occupations: vec![],
+        races: vec![],
+        nations: vec![],
+        biomes: vec![],
+        locations: vec![],
+        routes: vec![],
+        fauna: vec![],
+        events: vec![GlobalEvent {
+            id: DROUGHT,
+            name: "Засуха",
+            chance: 1.0,
+            duration: 2,
+            income: ResourceMap::filled(1.0),
+            upkeep: amounts(&[(MONEY, 0.5)]),
+        }],
+        params: vec![],
+        messages: vec![],
+    }
+}
+
+fn new_game() -> GameState {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let registry = ContentRegistry::load(base_pack(), vec![drought_pack()]).unwrap();
+        assert!(install(registry).is_ok());
+    });
+    let mut state = GameState::new("Игрок".to_string(), None, 0).unwrap();
+    state.verbose = false;
+    state
+}
+
+/// Сколько денег за последний интервал забрала засуха.
+fn drought_upkeep(state: &GameState) -> Option<f64> {
+    state
+        .attribution
+        .iter()
+        .find(|contribution| contribution.source == EffectSource::Event(DROUGHT))
+        .map(|contribution| contribution.before[MONEY] - contribution.after[MONEY])
+}
+
+#[test]
+fn calendar_is_off_by_default() {
+    let mut state = new_game();
+    assert!(state.world.calendar.is_none());
+    for biome in BIOMES.iter() {
+        for resource in RESOURCES.iter() {
+            assert_eq!(state.world.income_factor(biome.id, resource.id), 1.0);
+        }
+    }
+
+    for _ in 0..5 {
+        state.advance(&ApatheticStrategy).unwrap();
+        assert_eq!(drought_upkeep(&state), None);
+    }
+}
+
+#[test]
+fn mod_events_happen_when_the_calendar_is_on() {
+    let mut state = new_game();
+    state.world.calendar = Some(Calendar::default());
+
+    // Засуха начинается в конце первого интервала.
+    state.advance(&ApatheticStrategy).unwrap();
+    assert_eq!(drought_upkeep(&state), None);
+
+    state.advance(&ApatheticStrategy).unwrap();
+    assert_eq!(drought_upkeep(&state), Some(0.5));
+    let calendar = state.world.calendar.as_ref().unwrap();
+    assert!(calendar.active_events().any(|event| event.id == DROUGHT));
+}
+
+#[test]
+fn invalid_events_are_reported() {
+    let mut pack = drought_pack();
+    pack.events[0].chance = 1.5;
+    pack.events[0].upkeep = amounts(&[(ResourceId("karma"), 1.0)]);
+
+    let error = ContentRegistry::load(base_pack(), vec![pack])
+        .err()
+        .unwrap();
+    let SimErrorKind::Content(errors) = error.kind else {
+        panic!("ожидалась ошибка содержимого, получено: {error}");
+    };
+    assert_eq!(
+        errors.0,
+        vec![
+            ContentError::InvalidProbability {
+                kind: ContentKind::Event,
+                id: "drought".to_string(),
+                field: "chance",
+                value: 1.5,
+            },
+            ContentError::DanglingReference {
+                kind: ContentKind::Event,
+                id: "drought".to_string(),
+                target_kind: ContentKind::Resource,
+                target: "karma".to_string(),
+            },
+        ]
+    );
+}
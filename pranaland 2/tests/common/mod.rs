@@ -0,0 +1,362 @@
+//! Общие средства проверки свойств симуляции: генераторы случайного
+//! содержимого, состояний и стратегий, а также запуск проверки на множестве
+//! случайных примеров.
+//!
+//! Каждый пример порождается из собственного зерна, поэтому упавший пример
+//! можно повторить: `PRANALAND_PROPTEST_SEED=<зерно> PRANALAND_PROPTEST_CASES=1`.
+//! Случайное содержимое устанавливается один раз на тестовый бинарник,
+//! его зерно задаётся `PRANALAND_CONTENT_SEED`.
+
+#![allow(dead_code)]
+
+use enum_map::EnumMap;
+use pranaland::{
+    definitions::MAX_LOCATION_HISTORY_LEN,
+    fauna::{base_fauna, Fauna, FaunaBehaviour, FAUNA},
+    ids::{FaunaId, LocationId, NationId, ResourceId},
+    locations::{base_biomes, base_locations, Location, LOCATIONS},
+    nations::{base_nations, base_races, Nation, NATIONS},
+    occupations::{Occupation, Prerequisite},
+    params::{default_params, Param, ParamMap},
+    resources::{base_resources, Resource, RESOURCES},
+    travel::both_ways,
+    world::FaunaDynamics,
+    Action, ContentPack, ContentRegistry, GameState, OccupationId, Strategy, OCCUPATIONS,
+};
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::{cell::Cell, sync::Once};
+use strum::IntoEnumIterator;
+
+/// Значение переменной окружения `name`, либо `default`, если её нет.
+pub fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Проверяет свойство `property` на `cases` случайных примерах.
+/// Свойство возвращает описание нарушения, если оно нарушено.
+pub fn check(name: &str, cases: usize, property: impl Fn(&mut ChaCha8Rng) -> Result<(), String>) {
+    setup();
+
+    let seed = env_or("PRANALAND_PROPTEST_SEED", 0u64);
+    let cases = env_or("PRANALAND_PROPTEST_CASES", cases);
+    for case in 0..cases as u64 {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed + case);
+        if let Err(message) = property(&mut rng) {
+            panic!(
+                "Свойство \"{name}\" нарушено на примере с зерном {}: {message}",
+                seed + case
+            );
+        }
+    }
+}
+
+static SETUP: Once = Once::new();
+
+/// Устанавливает случайное содержимое игры. Вызывается до первого
+/// обращения к содержимому в каждом тесте.
+pub fn setup() {
+    SETUP.call_once(|| {
+        let mut rng = ChaCha8Rng::seed_from_u64(env_or("PRANALAND_CONTENT_SEED", 1337));
+        let registry = ContentRegistry::load(pranaland::base_pack(), vec![random_pack(&mut rng)])
+            .unwrap_or_else(|error| panic!("{error}"));
+        if pranaland::install(registry).is_err() {
+            panic!("Content is already installed!");
+        }
+    });
+}
+
+/// Строка, живущая до конца программы, - для идентификаторов и названий
+/// случайного содержимого.
+fn leak(string: String) -> &'static str {
+    Box::leak(string.into_boxed_str())
+}
+
+/// Случайное значение параметра: от нуля до трёх значений по умолчанию,
+/// а для вероятностей - от нуля до единицы.
+fn random_param(rng: &mut impl Rng, param: Param) -> f64 {
+    if param.is_probability() {
+        rng.gen_range(0.0..=1.0)
+    } else {
+        param.default_value() * rng.gen_range(0.0..=3.0)
+    }
+}
+
+/// Случайная таблица параметров. Иногда параметры остаются по умолчанию,
+/// иногда некоторые обнуляются.
+pub fn random_params(rng: &mut impl Rng) -> ParamMap {
+    if rng.gen_bool(0.2) {
+        return default_params();
+    }
+    EnumMap::from_fn(|param: Param| {
+        if rng.gen_bool(0.1) {
+            0.0
+        } else {
+            random_param(rng, param)
+        }
+    })
+}
+
+/// Случайное поведение фауны, вредящее одному из ресурсов `resources`:
+/// параметры урона и вероятности берутся из таблицы параметров, как
+/// у поведения в базовом содержимом.
+fn random_behaviour(rng: &mut impl Rng, resources: &[ResourceId]) -> FaunaBehaviour {
+    let probabilities: Vec<Param> = Param::iter().filter(|p| p.is_probability()).collect();
+    let amounts: Vec<Param> = Param::iter().filter(|p| !p.is_probability()).collect();
+    FaunaBehaviour::Aggressive {
+        resource: *resources.choose(rng).unwrap(),
+        chance: *probabilities.choose(rng).unwrap(),
+        damage: *amounts.choose(rng).unwrap(),
+    }
+}
+
+/// Случайный мод к базовому содержимому: несколько новых наций и локаций,
+/// занятие, доступное только в новых локациях, а иногда и новые ресурс
+/// и вид фауны. Эффекты заимствуются у базовых наций и локаций (в том числе
+/// для чужих локаций и наций), численность фауны, её динамика и поведение,
+/// маршруты и их стоимость - случайные.
+pub fn random_pack(rng: &mut impl Rng) -> ContentPack {
+    // Новый ресурс никакое занятие не восполняет, поэтому он тратится медленно.
+    let resources: Vec<Resource> = if rng.gen_bool(0.5) {
+        vec![Resource {
+            id: ResourceId("random-resource"),
+            name: "Случайный ресурс",
+            death_threshold: rng.gen_range(0.0..=1.0),
+            starting_amount: rng.gen_range(5.0..=20.0),
+            decay: rng.gen_range(0.0..=0.05),
+            idle_cost: rng.gen_range(0.0..=0.5),
+        }]
+    } else {
+        vec![]
+    };
+    let resource_ids: Vec<ResourceId> = base_resources()
+        .iter()
+        .chain(&resources)
+        .map(|res| res.id)
+        .collect();
+
+    let fauna: Vec<Fauna> = if rng.gen_bool(0.5) {
+        vec![Fauna {
+            id: FaunaId("random-fauna"),
+            name: "Случайная фауна",
+            name_singular: "Случайная особь",
+            behaviours: if rng.gen_bool(0.5) {
+                vec![random_behaviour(rng, &resource_ids)]
+            } else {
+                vec![]
+            },
+        }]
+    } else {
+        vec![]
+    };
+    let fauna_ids: Vec<FaunaId> = base_fauna()
+        .iter()
+        .chain(&fauna)
+        .map(|species| species.id)
+        .collect();
+
+    let occupations = vec![Occupation {
+        id: OccupationId("random-occupation"),
+        name: "Случайное занятие",
+        consumes: resource_ids
+            .iter()
+            .map(|&res| (res, rng.gen_range(0.0..=1.0)))
+            .collect(),
+        produces: *resource_ids.choose(rng).unwrap(),
+        fauna: vec![(*fauna_ids.choose(rng).unwrap(), rng.gen_range(0.5..=2.0))],
+        prerequisite: Some(Prerequisite {
+            description: "только в случайных локациях",
+            check: &|_, location| location.id.0.starts_with("random-location"),
+        }),
+    }];
+
+    let races = base_races();
+    let base_nations = base_nations();
+    let biomes = base_biomes();
+    let base_locations = base_locations();
+
+    let nations = (0..rng.gen_range(1..=4))
+        .map(|i| {
+            let donor = base_nations.choose(rng).unwrap();
+            Nation {
+                id: NationId(leak(format!("random-nation-{i}"))),
+                race: races.choose(rng).unwrap().id,
+                name_plural: leak(format!("Случайные №{}", i + 1)),
+                name_singular: leak(format!("Случайный №{}", i + 1)),
+                pre_effect: donor.pre_effect,
+                post_effect: if rng.gen_bool(0.8) {
+                    donor.post_effect
+                } else {
+                    None
+                },
+            }
+        })
+        .collect();
+
+    let mut locations = Vec::new();
+    let mut routes = Vec::new();
+    for i in 0..rng.gen_range(1..=4) {
+        let id = LocationId(leak(format!("random-location-{i}")));
+        let biome = biomes.choose(rng).unwrap().id;
+        let donor = base_locations.choose(rng).unwrap();
+
+        // Маршруты внутри биома создаются автоматически, поэтому новая
+        // локация связывается с локацией из другого биома.
+        let neighbour = base_locations
+            .iter()
+            .filter(|location| location.biome != biome)
+            .collect::<Vec<_>>()
+            .choose(rng)
+            .unwrap()
+            .id;
+        let cost = resource_ids
+            .iter()
+            .map(|&res| (res, rng.gen_range(0.0..=2.0)))
+            .collect();
+        routes.extend(both_ways(id, neighbour, cost, rng.gen_range(0..=2)));
+
+        locations.push(Location {
+            id,
+            name: leak(format!("Случайная локация №{}", i + 1)),
+            biome,
+            fauna: fauna_ids
+                .iter()
+                .map(|&species| (species, rng.gen_range(0..=5)))
+                .collect(),
+            effect_description: donor.effect_description,
+            pre_effect: donor.pre_effect,
+            post_effect: donor.post_effect,
+            fauna_behaviours: if rng.gen_bool(0.5) {
+                vec![(
+                    *fauna_ids.choose(rng).unwrap(),
+                    random_behaviour(rng, &resource_ids),
+                )]
+            } else {
+                vec![]
+            },
+            dynamics: rng.gen_bool(0.7).then(|| FaunaDynamics {
+                depletion: rng.gen_range(0.0..=0.5),
+                regrowth: rng.gen_range(0.0..=0.5),
+                capacity: rng.gen_range(0.5..=1.0),
+                migration: rng.gen_range(0.0..=0.1),
+            }),
+        });
+    }
+
+    ContentPack {
+        name: "Случайный мод",
+        resources,
+        occupations,
+        races: vec![],
+        nations,
+        biomes: vec![],
+        locations,
+        routes,
+        fauna,
+        events: vec![],
+        params: vec![],
+        messages: vec![],
+    }
+}
+
+/// Случайная локация установленного содержимого.
+pub fn random_location(rng: &mut impl Rng) -> &'static Location {
+    &LOCATIONS[rng.gen_range(0..LOCATIONS.len())]
+}
+
+/// Случайная нация установленного содержимого.
+pub fn random_nation(rng: &mut impl Rng) -> &'static Nation {
+    &NATIONS[rng.gen_range(0..NATIONS.len())]
+}
+
+/// Случайное действие - не обязательно допустимое.
+pub fn random_action(rng: &mut impl Rng) -> Action {
+    let occupations: Vec<OccupationId> = OCCUPATIONS.iter().map(|o| o.id).collect();
+    Action {
+        destination: rng.gen_bool(0.5).then(|| random_location(rng)),
+        occupation: if rng.gen_bool(0.8) {
+            occupations.choose(rng).copied()
+        } else {
+            None
+        },
+    }
+}
+
+/// Новая игра без вывода в STDOUT со случайными нацией и параметрами правил.
+pub fn random_game(rng: &mut impl Rng) -> GameState {
+    let nation = rng.gen_bool(0.8).then(|| random_nation(rng));
+    let mut state = GameState::new("Игрок".to_string(), nation, rng.gen()).unwrap();
+    state.verbose = false;
+    state.params = random_params(rng);
+    state
+}
+
+/// Игра в произвольном, не обязательно достижимом состоянии: ресурсы могут
+/// быть отрицательными, а фауны - больше, чем в локации.
+pub fn random_state(rng: &mut impl Rng) -> GameState {
+    let mut state = random_game(rng);
+    state.location = random_location(rng);
+    state.location_history = (0..rng.gen_range(0..MAX_LOCATION_HISTORY_LEN))
+        .map(|_| random_location(rng))
+        .chain(std::iter::once(state.location))
+        .collect();
+    state.time_spent_in_this_location = rng.gen_range(0..5);
+    state.time_passed = rng.gen_range(0..100);
+    state.effective_fauna = FAUNA
+        .iter()
+        .map(|species| (species.id, rng.gen_range(0..8)))
+        .collect();
+    state.player.resources = RESOURCES
+        .iter()
+        .map(|res| (res.id, rng.gen_range(-5.0..50.0)))
+        .collect();
+    state.resource_change = RESOURCES
+        .iter()
+        .map(|res| (res.id, rng.gen_range(-5.0..5.0)))
+        .collect();
+    state
+}
+
+/// Стратегия, совершающая действия из заранее заданного списка по очереди,
+/// а когда список заканчивается - ничего не делающая.
+pub struct ScriptedStrategy {
+    pub actions: Vec<Action>,
+    next: Cell<usize>,
+}
+
+impl ScriptedStrategy {
+    pub fn new(actions: Vec<Action>) -> Self {
+        Self {
+            actions,
+            next: Cell::new(0),
+        }
+    }
+
+    /// Стратегия из `len` случайных действий.
+    pub fn random(rng: &mut impl Rng, len: usize) -> Self {
+        Self::new((0..len).map(|_| random_action(rng)).collect())
+    }
+
+    /// Закончились ли действия.
+    pub fn is_finished(&self) -> bool {
+        self.next.get() >= self.actions.len()
+    }
+}
+
+impl Strategy for ScriptedStrategy {
+    fn name(&self) -> &'static str {
+        "Сценарий"
+    }
+
+    fn take_action(&self, _state: &mut GameState) -> Action {
+        let i = self.next.get();
+        self.next.set(i + 1);
+        self.actions.get(i).copied().unwrap_or(Action {
+            destination: None,
+            occupation: None,
+        })
+    }
+}
@@ -0,0 +1,63 @@
+//! Правила рождения проверяются при создании.
+
+use pranaland::{
+    definitions::{SimError, SimErrorKind},
+    demography::{Demography, Reproduction},
+    strategies::ApatheticStrategy,
+    World,
+};
+
+fn invalid_field<T>(result: Result<T, SimError>) -> &'static str {
+    match result.err().map(|error| error.kind) {
+        Some(SimErrorKind::InvalidDemography { field, .. }) => field,
+        _ => panic!("ожидалась ошибка в правилах рождения"),
+    }
+}
+
+fn demography(
+    birth_chance: f64,
+    reproduction: Option<Reproduction>,
+) -> Result<Demography, SimError> {
+    Demography::new(
+        birth_chance,
+        reproduction,
+        50,
+        Box::new(|| Box::new(ApatheticStrategy)),
+    )
+}
+
+#[test]
+fn chances_must_be_probabilities() {
+    assert_eq!(invalid_field(Reproduction::new(25.0, 1.5)), "chance");
+    assert_eq!(invalid_field(Reproduction::new(25.0, -0.1)), "chance");
+    assert_eq!(invalid_field(Reproduction::new(25.0, f64::NAN)), "chance");
+    assert_eq!(invalid_field(demography(2.0, None)), "birth_chance");
+    assert_eq!(invalid_field(demography(f64::NAN, None)), "birth_chance");
+}
+
+#[test]
+fn parent_must_survive_giving_birth() {
+    // Ребёнок получает по 10 единиц каждого ресурса, а при 0 Игрок погибает.
+    assert_eq!(invalid_field(Reproduction::new(10.0, 0.5)), "threshold");
+    assert_eq!(invalid_field(Reproduction::new(5.0, 0.5)), "threshold");
+    assert_eq!(
+        invalid_field(Reproduction::new(f64::INFINITY, 0.5)),
+        "threshold"
+    );
+
+    let reproduction = Reproduction::new(10.5, 0.5).unwrap();
+    assert_eq!(reproduction.threshold(), 10.5);
+    assert_eq!(reproduction.chance(), 0.5);
+}
+
+#[test]
+fn valid_demography_grows_the_world() {
+    let reproduction = Reproduction::new(11.0, 1.0).unwrap();
+    let mut world = World::new(7);
+    world.demography = Some(demography(1.0, Some(reproduction)).unwrap());
+
+    for _ in 0..5 {
+        world.advance().unwrap();
+    }
+    assert!(world.alive().count() > 0);
+}
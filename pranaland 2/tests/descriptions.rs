@@ -0,0 +1,31 @@
+//! Описания эффектов локаций следуют за параметрами правил.
+
+use pranaland::{
+    ids::LocationId,
+    locations::LOCATIONS,
+    params::{default_content_params, default_params, Param},
+};
+
+#[test]
+fn descriptions_have_no_unfilled_placeholders() {
+    for location in LOCATIONS.iter() {
+        let description = location.effect_description(&default_params(), &default_content_params());
+        assert!(!description.contains('{'), "{}: {description}", location.id);
+    }
+}
+
+#[test]
+fn descriptions_render_current_params() {
+    let dolbesburg = LocationId("dolbesburg").try_get().unwrap();
+    let mut params = default_params();
+
+    let description = dolbesburg.effect_description(&params, &default_content_params());
+    assert!(description.contains("20%"), "{description}");
+    assert!(description.contains("30%"), "{description}");
+
+    params[Param::DolbesburgMoney] = 1.5;
+    params[Param::DolbesburgSatisfaction] = 1.125;
+    let description = dolbesburg.effect_description(&params, &default_content_params());
+    assert!(description.contains("50%"), "{description}");
+    assert!(description.contains("12.5%"), "{description}");
+}
@@ -0,0 +1,72 @@
+//! Динамика фауны включается явно и не меняет базовые правила.
+
+use pranaland::{
+    definitions::SimErrorKind, locations::LOCATIONS, nations::NATIONS, strategies::RandomStrategy,
+    world::FaunaDynamics, GameState,
+};
+
+const STEPS: usize = 40;
+
+fn play(seed: u64, dynamics: Option<FaunaDynamics>) -> GameState {
+    let mut state = GameState::new("Игрок".to_string(), Some(&NATIONS[0]), seed).unwrap();
+    state.verbose = false;
+    state.world.default_dynamics = dynamics;
+    while state.player.is_alive() && state.time_passed < STEPS {
+        state.advance(&RandomStrategy).unwrap();
+    }
+    state
+}
+
+#[test]
+fn base_fauna_is_static_by_default() {
+    assert!(LOCATIONS.iter().all(|location| location.dynamics.is_none()));
+
+    for seed in 0..20 {
+        let state = play(seed, None);
+        for (location, fauna) in LOCATIONS.iter().zip(&state.world.fauna) {
+            assert!(
+                fauna
+                    .iter()
+                    .all(|(f, &population)| population == location.fauna[f] as f64),
+                "зерно {seed}, {}: фауна изменилась без динамики",
+                location.id
+            );
+        }
+    }
+}
+
+#[test]
+fn standard_dynamics_can_be_enabled() {
+    let changed = (0..20).any(|seed| {
+        let state = play(seed, Some(FaunaDynamics::STANDARD));
+        LOCATIONS
+            .iter()
+            .zip(&state.world.fauna)
+            .any(|(location, fauna)| {
+                fauna
+                    .iter()
+                    .any(|(f, &population)| population != location.fauna[f] as f64)
+            })
+    });
+    assert!(changed, "включённая динамика ни разу не изменила фауну");
+}
+
+#[test]
+fn invalid_default_dynamics_is_an_error() {
+    let mut state = GameState::new("Игрок".to_string(), Some(&NATIONS[0]), 0).unwrap();
+    state.verbose = false;
+    state.world.default_dynamics = Some(FaunaDynamics {
+        capacity: 1.5,
+        ..FaunaDynamics::STANDARD
+    });
+
+    let error = state.advance(&RandomStrategy).err().unwrap();
+    assert!(matches!(
+        error.kind,
+        SimErrorKind::InvalidDynamics {
+            field: "capacity",
+            ..
+        }
+    ));
+    assert_eq!(state.time_passed, 0);
+}
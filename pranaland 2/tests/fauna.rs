@@ -0,0 +1,146 @@
+//! Виды фауны, описанные в наборах содержимого.
+
+use pranaland::{
+    base_pack,
+    definitions::SimErrorKind,
+    fauna::{counts, Fauna, FaunaBehaviour, SISYANDRA},
+    ids::{BiomeId, FaunaId, LocationId, OccupationId},
+    locations::Location,
+    occupations::Occupation,
+    params::Param,
+    registry::{ContentError, ContentKind},
+    resources::{amounts, HEALTH, MONEY},
+    world::FaunaDynamics,
+    ContentPack, ContentRegistry,
+};
+
+const GRUSHANDRA: FaunaId = FaunaId("grushandra");
+
+/// Мод с новым видом фауны, локацией, где он живёт, и занятием,
+/// которое он питает.
+fn grushandra_pack() -> ContentPack {
+    ContentPack {
+        name: "Грушандры",
+        resources: vec![],
+        occupations: vec![Occupation {
+            id: OccupationId("grushing"),
+            name: "Грушить",
+            consumes: amounts(&[(HEALTH, 1.0)]),
+            produces: MONEY,
+            fauna: vec![(GRUSHANDRA, 1.0)],
+            prerequisite: None,
+        }],
+        races: vec![],
+        nations: vec![],
+        biomes: vec![],
+        locations: vec![Location {
+            id: LocationId("grushevka"),
+            name: "Грушевка",
+            biome: BiomeId("workland"),
+            fauna: counts(&[(GRUSHANDRA, 4), (SISYANDRA, 1)]),
+            effect_description: "Ничего особенного.",
+            pre_effect: None,
+            post_effect: None,
+            fauna_behaviours: vec![],
+            dynamics: None,
+        }],
+        routes: vec![],
+        fauna: vec![Fauna {
+            id: GRUSHANDRA,
+            name: "Грушандры",
+            name_singular: "Грушандра",
+            behaviours: vec![],
+        }],
+        events: vec![],
+        params: vec![],
+        messages: vec![],
+    }
+}
+
+#[test]
+fn mod_adds_a_species() {
+    let registry = ContentRegistry::load(base_pack(), vec![grushandra_pack()]).unwrap();
+
+    let ids: Vec<FaunaId> = registry.fauna.iter().map(|fauna| fauna.id).collect();
+    assert_eq!(ids.len(), 4);
+    assert_eq!(ids.last(), Some(&GRUSHANDRA));
+
+    let location = registry.locations.last().unwrap();
+    assert_eq!(location.count(GRUSHANDRA), 4);
+    assert_eq!(location.count(FaunaId("slesandra")), 0);
+}
+
+#[test]
+fn unknown_species_is_reported() {
+    let mut pack = grushandra_pack();
+    pack.fauna.clear();
+
+    let error = ContentRegistry::load(base_pack(), vec![pack])
+        .err()
+        .unwrap();
+    let SimErrorKind::Content(errors) = error.kind else {
+        panic!("ожидалась ошибка содержимого, получено: {error}");
+    };
+    assert_eq!(
+        errors.0,
+        vec![
+            ContentError::DanglingReference {
+                kind: ContentKind::Occupation,
+                id: "grushing".to_string(),
+                target_kind: ContentKind::Fauna,
+                target: "grushandra".to_string(),
+            },
+            ContentError::DanglingReference {
+                kind: ContentKind::Location,
+                id: "grushevka".to_string(),
+                target_kind: ContentKind::Fauna,
+                target: "grushandra".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn every_invalid_share_and_chance_is_reported() {
+    let mut pack = grushandra_pack();
+    pack.locations[0].dynamics = Some(FaunaDynamics {
+        depletion: -0.1,
+        regrowth: 1.5,
+        capacity: 2.0,
+        migration: f64::NAN,
+    });
+    pack.occupations[0].fauna[0].1 = -1.0;
+    pack.fauna[0].behaviours.push(FaunaBehaviour::Aggressive {
+        resource: HEALTH,
+        chance: Param::FaunaYield,
+        damage: Param::FaunaYield,
+    });
+
+    let error = ContentRegistry::load(base_pack(), vec![pack])
+        .err()
+        .unwrap();
+    let SimErrorKind::Content(errors) = error.kind else {
+        panic!("ожидалась ошибка содержимого, получено: {error}");
+    };
+    let fields: Vec<&str> = errors
+        .0
+        .iter()
+        .map(|error| match error {
+            ContentError::InvalidProbability { field, .. }
+            | ContentError::NotAProbability { field, .. }
+            | ContentError::NegativeValue { field, .. } => *field,
+            other => panic!("неожиданная ошибка: {other}"),
+        })
+        .collect();
+    assert_eq!(
+        fields,
+        vec![
+            "fauna",
+            "depletion",
+            "regrowth",
+            "capacity",
+            "migration",
+            "chance"
+        ]
+    );
+}
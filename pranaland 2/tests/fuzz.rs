@@ -0,0 +1,149 @@
+//! Фаззинг `GameState::advance` произвольными стратегиями.
+//!
+//! `fuzz_advance` - цель для фаззера: все решения, включая зерно игры,
+//! параметры правил и поведение стратегии, берутся из входных байтов.
+//! Здесь она запускается на случайных входах (их количество задаётся
+//! `PRANALAND_FUZZ_RUNS`), но её можно подключить и к cargo-fuzz:
+//! `fuzz_target!(|data: &[u8]| fuzz_advance(data).unwrap())`.
+
+mod common;
+
+use common::{check, env_or, setup};
+use pranaland::{
+    definitions::SimErrorKind,
+    locations::LOCATIONS,
+    nations::NATIONS,
+    params::{default_params, Param},
+    Action, GameState, OccupationId, Strategy, OCCUPATIONS,
+};
+use rand::Rng;
+use std::{
+    cell::Cell,
+    panic::{self, AssertUnwindSafe},
+};
+use strum::IntoEnumIterator;
+
+/// Сколько интервалов длится одна игра, даже если байты не закончились.
+const MAX_STEPS: usize = 500;
+
+/// Стратегия, каждое решение которой задаётся очередными байтами входа.
+/// Она может выбирать недопустимые действия, пользоваться генератором
+/// случайных чисел игры и паниковать.
+struct ByteStrategy<'a> {
+    data: &'a [u8],
+    position: Cell<usize>,
+}
+
+impl ByteStrategy<'_> {
+    fn next(&self) -> u8 {
+        let position = self.position.get();
+        self.position.set(position + 1);
+        self.data.get(position).copied().unwrap_or(0)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.position.get() >= self.data.len()
+    }
+}
+
+impl Strategy for ByteStrategy<'_> {
+    fn name(&self) -> &'static str {
+        "Байты"
+    }
+
+    fn take_action(&self, state: &mut GameState) -> Action {
+        match self.next() % 8 {
+            // Допустимое действие по номеру.
+            0 | 1 => {
+                let actions = state.legal_actions();
+                let i = self.next() as usize;
+                if let Some(&action) = actions.get(i % actions.len().max(1)) {
+                    return action;
+                }
+            }
+            // Стратегия может тратить случайные числа игры.
+            2 => {
+                state.rng.gen::<u64>();
+            }
+            7 => panic!("Стратегия сломалась"),
+            _ => {}
+        }
+
+        let destination = match self.next() as usize {
+            0 => None,
+            i => Some(&LOCATIONS[i % LOCATIONS.len()]),
+        };
+        let occupations: Vec<OccupationId> = OCCUPATIONS.iter().map(|o| o.id).collect();
+        let occupation = occupations.get(self.next() as usize % (occupations.len() + 1));
+        Action {
+            destination,
+            occupation: occupation.copied(),
+        }
+    }
+}
+
+/// Играет по байтам `data` с проверкой инвариантов. Недопустимые действия
+/// и паника стратегии - ожидаемые ошибки, всё остальное - найденная ошибка.
+fn fuzz_advance(data: &[u8]) -> Result<(), String> {
+    setup();
+
+    let mut seed = [0; 8];
+    for (byte, &value) in seed.iter_mut().zip(data) {
+        *byte = value;
+    }
+    let data = data.get(8..).unwrap_or_default();
+    let strategy = ByteStrategy {
+        data,
+        position: Cell::new(0),
+    };
+
+    let nation = match strategy.next() as usize {
+        0 => None,
+        i => Some(&NATIONS[i % NATIONS.len()]),
+    };
+    let mut state = GameState::new("Игрок".to_string(), nation, u64::from_le_bytes(seed)).unwrap();
+    state.verbose = false;
+    state.check_invariants = true;
+    for param in Param::iter() {
+        let byte = strategy.next() as f64;
+        state.params[param] = if param.is_probability() {
+            byte / 255.0
+        } else {
+            default_params()[param] * byte / 64.0
+        };
+    }
+
+    while !strategy.is_finished() && state.player.is_alive() && state.time_passed < MAX_STEPS {
+        match state.advance(&strategy) {
+            Ok(()) => {}
+            Err(error) => match error.kind {
+                SimErrorKind::IllegalAction(_) | SimErrorKind::StrategyPanicked { .. } => {}
+                _ => return Err(error.to_string()),
+            },
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn fuzz_edge_inputs() {
+    for data in [vec![], vec![0; 64], vec![0xFF; 64], (0..=255).collect()] {
+        if let Err(error) = fuzz_advance(&data) {
+            panic!("Вход {data:?}: {error}");
+        }
+    }
+}
+
+#[test]
+fn fuzz_random_inputs() {
+    check(
+        "advance с произвольной стратегией",
+        env_or("PRANALAND_FUZZ_RUNS", 500),
+        |rng| {
+            let len = rng.gen_range(0..512);
+            let data: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            panic::catch_unwind(AssertUnwindSafe(|| fuzz_advance(&data)))
+                .map_err(|_| "движок запаниковал".to_string())?
+        },
+    );
+}
@@ -0,0 +1,64 @@
+//! Проверка параметров генетического алгоритма и сведения оценок по нациям.
+
+use pranaland::{
+    definitions::{SimError, SimErrorKind},
+    genetic::{evolve, GeneticConfig},
+    robust::RobustObjective,
+};
+
+fn invalid_field(result: Result<GeneticConfig, SimError>) -> &'static str {
+    match result.err().map(|error| error.kind) {
+        Some(SimErrorKind::InvalidGenetic { field, .. }) => field,
+        other => panic!("ожидалась ошибка параметров, получено: {other:?}"),
+    }
+}
+
+#[test]
+fn invalid_settings_are_rejected() {
+    assert_eq!(
+        invalid_field(GeneticConfig::new(2, 2, 3, 0.2, 3.0, 20.0)),
+        "elite"
+    );
+    assert_eq!(
+        invalid_field(GeneticConfig::new(0, 0, 3, 0.2, 3.0, 20.0)),
+        "elite"
+    );
+    assert_eq!(
+        invalid_field(GeneticConfig::new(5, 1, 0, 0.2, 3.0, 20.0)),
+        "tournament_size"
+    );
+    assert_eq!(
+        invalid_field(GeneticConfig::new(5, 1, 3, 1.5, 3.0, 20.0)),
+        "mutation_rate"
+    );
+    assert_eq!(
+        invalid_field(GeneticConfig::new(5, 1, 3, f64::NAN, 3.0, 20.0)),
+        "mutation_rate"
+    );
+    assert_eq!(
+        invalid_field(GeneticConfig::new(5, 1, 3, 0.2, -1.0, 20.0)),
+        "mutation_scale"
+    );
+    assert_eq!(
+        invalid_field(GeneticConfig::new(5, 1, 3, 0.2, 3.0, f64::INFINITY)),
+        "max_threshold"
+    );
+}
+
+#[test]
+fn smallest_valid_population_evolves() {
+    let mut config = GeneticConfig::new(1, 0, 1, 1.0, 0.0, 0.0).unwrap();
+    config.generations = 2;
+
+    let best = evolve(&config, &|genome| genome.thresholds.values().sum());
+    assert_eq!(best.fitness, 0.0);
+    assert_eq!(config.population(), 1);
+}
+
+#[test]
+fn robust_objectives_need_at_least_one_nation() {
+    assert_eq!(RobustObjective::Mean.combine(&[]), None);
+    assert_eq!(RobustObjective::WorstCase.combine(&[]), None);
+    assert_eq!(RobustObjective::Mean.combine(&[1.0, 2.0]), Some(1.5));
+    assert_eq!(RobustObjective::WorstCase.combine(&[1.0, 2.0]), Some(1.0));
+}
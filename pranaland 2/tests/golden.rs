@@ -0,0 +1,144 @@
+//! Эталонные записи игр: для каждой нации и нескольких зёрен - полный журнал
+//! событий игры со случайной стратегией на базовом содержимом.
+//!
+//! Эталоны лежат в `tests/golden/`. Если изменение движка или содержимого
+//! меняет ход игр намеренно, эталоны перезаписываются командой
+//! `PRANALAND_UPDATE_GOLDEN=1 cargo test --test golden`, а изменения в них
+//! проверяются при ревью вместе с кодом.
+
+use pranaland::{
+    nations::{Nation, NATIONS},
+    strategies::RandomStrategy,
+    GameState, RESOURCES,
+};
+use std::{fmt::Write, fs, path::PathBuf};
+
+const SEEDS: [u64; 3] = [1, 2, 3];
+
+/// Игры длиннее этого обрываются, чтобы эталоны оставались читаемыми.
+const MAX_STEPS: usize = 60;
+
+/// Сколько строк различий выводить, прежде чем сократить отчёт.
+const MAX_DIFF_LINES: usize = 40;
+
+/// Журнал игры за нацию `nation` с зерном `seed`: перед событиями каждого
+/// интервала - его номер, локация и ресурсы Игрока в начале интервала.
+fn record(nation: &'static Nation, seed: u64) -> String {
+    let mut state = GameState::new("Жора".to_string(), Some(nation), seed).unwrap();
+    state.verbose = false;
+    state.events = Some(Vec::new());
+
+    let mut trace = String::new();
+    while state.player.is_alive() && state.time_passed < MAX_STEPS {
+        let resources: Vec<String> = RESOURCES
+            .iter()
+            .map(|res| format!("{} {:.4}", res.name(), state.player.resources[res.id]))
+            .collect();
+        writeln!(
+            trace,
+            "# интервал {}, {}, {}",
+            state.time_passed + 1,
+            state.location.id,
+            resources.join(", ")
+        )
+        .unwrap();
+
+        let result = state.advance(&RandomStrategy);
+        for event in state.events.as_mut().unwrap().drain(..) {
+            writeln!(trace, "{event}").unwrap();
+        }
+        if let Err(error) = result {
+            writeln!(trace, "# ошибка: {error}").unwrap();
+            break;
+        }
+    }
+
+    match state.player.death_cause() {
+        Some(cause) => writeln!(
+            trace,
+            "# итог: погибает, закончился ресурс \"{}\"",
+            cause.name()
+        ),
+        None => writeln!(trace, "# итог: жив после {} интервалов", state.time_passed),
+    }
+    .unwrap();
+    trace
+}
+
+/// Различия между эталоном `expected` и записью `actual` построчно,
+/// в виде `-` (было) и `+` (стало) с номерами строк эталона.
+fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    // Наибольшая общая подпоследовательность строк: lcs[i][j] - для
+    // хвостов expected[i..] и actual[j..].
+    let mut lcs = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut report = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            i += 1;
+            j += 1;
+        } else if j == actual.len() || (i < expected.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            report.push(format!("{:>5} - {}", i + 1, expected[i]));
+            i += 1;
+        } else {
+            report.push(format!("{:>5} + {}", i + 1, actual[j]));
+            j += 1;
+        }
+    }
+
+    let total = report.len();
+    if total > MAX_DIFF_LINES {
+        report.truncate(MAX_DIFF_LINES);
+        report.push(format!("... и ещё {} строк", total - MAX_DIFF_LINES));
+    }
+    report.join("\n")
+}
+
+#[test]
+fn traces_match_golden_files() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    let update = std::env::var_os("PRANALAND_UPDATE_GOLDEN").is_some();
+    if update {
+        fs::create_dir_all(&dir).unwrap();
+    }
+
+    let mut failures = Vec::new();
+    for nation in NATIONS.iter() {
+        for seed in SEEDS {
+            let path = dir.join(format!("{}-{seed}.txt", nation.id));
+            let actual = record(nation, seed);
+
+            if update {
+                fs::write(&path, &actual).unwrap();
+                continue;
+            }
+            match fs::read_to_string(&path) {
+                Ok(expected) if expected == actual => {}
+                Ok(expected) => {
+                    failures.push(format!("{}:\n{}", path.display(), diff(&expected, &actual)))
+                }
+                Err(error) => failures.push(format!("{}: {error}", path.display())),
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "Ход игр отличается от эталонов. Если изменение намеренное, обновите \
+         эталоны: PRANALAND_UPDATE_GOLDEN=1 cargo test --test golden\n\n{}",
+        failures.join("\n\n")
+    );
+}
@@ -0,0 +1,108 @@
+//! Каталоги сообщений и подстановка аргументов в шаблоны.
+
+use pranaland::{
+    base_pack,
+    i18n::{render, set_locale, Locale},
+    ids::{LocationId, ResourceId},
+    travel::RouteDef,
+    ContentPack, ContentRegistry, Resource, ResourceMap,
+};
+use std::collections::HashSet;
+
+fn keys(catalog: &str) -> HashSet<&str> {
+    catalog
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('=').map(|(key, _)| key.trim()))
+        .collect()
+}
+
+#[test]
+fn every_translated_message_exists_in_russian() {
+    let ru = keys(include_str!("../locales/ru.txt"));
+    let en = keys(include_str!("../locales/en.txt"));
+
+    // Названия содержимого берутся из наборов, а не из русского каталога.
+    let content = [
+        "resource.",
+        "occupation.",
+        "fauna.",
+        "biome.",
+        "location.",
+        "race.",
+        "nation.",
+        "event.",
+    ];
+    let missing: Vec<&&str> = en
+        .iter()
+        .filter(|key| !ru.contains(*key))
+        .filter(|key| !content.iter().any(|prefix| key.starts_with(prefix)))
+        .collect();
+    assert!(missing.is_empty(), "нет в русском каталоге: {missing:?}");
+}
+
+#[test]
+fn placeholders_are_aligned_to_width() {
+    let args: [(&str, &dyn std::fmt::Display); 2] = [("name", &"Жора"), ("x", &1.25)];
+    assert_eq!(
+        render("[{name:<6}][{name:>6}][{name:^6}][{name}]", &args),
+        "[Жора  ][  Жора][ Жора ][Жора]"
+    );
+    assert_eq!(
+        render("[{x:>6.1}][{x:<6.1}][{x:6}][{x:.3}][{x:%}]", &args),
+        "[   1.2][1.2   ][  1.25][1.250][125]"
+    );
+}
+
+#[test]
+fn content_errors_are_translated() {
+    let karma = Resource {
+        id: ResourceId("karma"),
+        name: "Karma",
+        death_threshold: 0.0,
+        starting_amount: 1.0,
+        decay: 2.0,
+        idle_cost: 0.0,
+    };
+    let pack = ContentPack {
+        name: "Broken",
+        resources: vec![karma.clone(), karma],
+        occupations: vec![],
+        races: vec![],
+        nations: vec![],
+        biomes: vec![],
+        locations: vec![],
+        routes: vec![RouteDef {
+            from: LocationId("nowhere"),
+            to: LocationId("balbesburg"),
+            cost: ResourceMap::default(),
+            extra_intervals: 0,
+        }],
+        fauna: vec![],
+        events: vec![],
+        params: vec![],
+        messages: vec![(Locale::En, "karma.name: Karma")],
+    };
+
+    set_locale(Locale::En);
+    let errors = ContentRegistry::load(base_pack(), vec![pack])
+        .err()
+        .unwrap()
+        .to_string();
+    let unknown = LocationId("nowhere").try_get().err().unwrap().to_string();
+    set_locale(Locale::Ru);
+
+    for part in [
+        "Broken: identifier karma (resource) occurs more than once",
+        "resource karma: decay = 2 is outside [0, 1]",
+        "route nowhere -> balbesburg refers to a missing object (location nowhere)",
+        "Broken: malformed message catalog line \"karma.name: Karma\"",
+    ] {
+        assert!(errors.contains(part), "нет {part:?} в {errors:?}");
+    }
+    // Ни одного русского слова: всё содержимое мода названо латиницей.
+    let cyrillic = |c: char| ('а'..='я').contains(&c.to_lowercase().next().unwrap_or(c));
+    assert!(!errors.contains(cyrillic), "{errors}");
+    assert_eq!(unknown, "unknown identifier (location): nowhere");
+}
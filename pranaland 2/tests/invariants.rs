@@ -0,0 +1,74 @@
+//! Проверка инвариантов и отчёт для повтора интервала.
+
+use pranaland::{
+    fauna::SLESANDRA,
+    invariants::{violations, Violation},
+    nations::NATIONS,
+    world::FaunaDynamics,
+    GameState, Replay,
+};
+
+fn state() -> GameState {
+    let mut state = GameState::new("Игрок".to_string(), Some(&NATIONS[0]), 7).unwrap();
+    state.verbose = false;
+    state
+}
+
+#[test]
+fn fauna_is_bounded_by_the_location() {
+    let before = state();
+    let living = before.location.fauna[SLESANDRA];
+
+    // Фауна мира истощилась, но вернуть её к числу из описания локации можно.
+    let mut after = before.clone();
+    after.world.fauna[after.location.index().unwrap()][SLESANDRA] = 0.0;
+    after.effective_fauna[SLESANDRA] = living;
+    assert!(!violations(&before, &after)
+        .iter()
+        .any(|violation| matches!(violation, Violation::FaunaExceeded { .. })));
+
+    // Сверх описания - только с объявленной прибавкой.
+    after.effective_fauna[SLESANDRA] = living + 1;
+    assert!(
+        violations(&before, &after).contains(&Violation::FaunaExceeded {
+            fauna: SLESANDRA,
+            effective: living + 1,
+            allowed: living,
+        })
+    );
+
+    after.effective_fauna[SLESANDRA] = living;
+    after.declare_fauna(SLESANDRA, 1);
+    assert!(!violations(&before, &after)
+        .iter()
+        .any(|violation| matches!(violation, Violation::FaunaExceeded { .. })));
+}
+
+#[test]
+fn replay_names_everything_needed_to_repeat_the_interval() {
+    let mut state = state();
+    state.time_passed = 12;
+    state.world.default_dynamics = Some(FaunaDynamics::STANDARD);
+    let replay = Replay {
+        strategy: "Случайная стратегия",
+        state,
+    };
+
+    let text = replay.to_string();
+    for part in [
+        "7",
+        "12",
+        "Случайная стратегия",
+        NATIONS[0].id.0,
+        "Дурляндия",
+        "OccupationCost = 1",
+        "depletion: 0.15",
+    ] {
+        assert!(text.contains(part), "нет {part:?} в {text:?}");
+    }
+
+    let debug = format!("{replay:?}");
+    for field in ["seed", "step", "nation", "packs", "params", "dynamics"] {
+        assert!(debug.contains(field), "нет {field:?} в {debug:?}");
+    }
+}
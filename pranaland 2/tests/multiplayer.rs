@@ -0,0 +1,74 @@
+//! Мир из нескольких жителей: ошибки стратегий не останавливают остальных.
+
+use pranaland::{
+    definitions::{Action, GameState, SimErrorKind, Strategy},
+    ids::OccupationId,
+    strategies::ApatheticStrategy,
+    World,
+};
+
+/// Выбирает занятие, которого нет в содержимом игры.
+struct UnknownOccupationStrategy;
+
+impl Strategy for UnknownOccupationStrategy {
+    fn name(&self) -> &'static str {
+        "Несуществующее занятие"
+    }
+
+    fn take_action(&self, _state: &mut GameState) -> Action {
+        Action {
+            destination: None,
+            occupation: Some(OccupationId("sleeping")),
+        }
+    }
+}
+
+struct PanickingStrategy;
+
+impl Strategy for PanickingStrategy {
+    fn name(&self) -> &'static str {
+        "Паникёр"
+    }
+
+    fn take_action(&self, _state: &mut GameState) -> Action {
+        panic!("Стратегия сломалась")
+    }
+}
+
+#[test]
+fn strategy_errors_are_recorded_on_the_inhabitant() {
+    let mut world = World::new(5);
+    let strategies: [Box<dyn Strategy>; 3] = [
+        Box::new(UnknownOccupationStrategy),
+        Box::new(PanickingStrategy),
+        Box::new(ApatheticStrategy),
+    ];
+    for (i, strategy) in strategies.into_iter().enumerate() {
+        world
+            .add_inhabitant(format!("Житель №{}", i + 1), None, strategy)
+            .unwrap();
+    }
+
+    world.advance().unwrap();
+    world.advance().unwrap();
+
+    let errors: Vec<_> = world
+        .inhabitants
+        .iter()
+        .map(|inhabitant| &inhabitant.errors)
+        .collect();
+    assert_eq!(errors[0].len(), 2);
+    assert!(errors[0]
+        .iter()
+        .all(|error| matches!(error.kind, SimErrorKind::IllegalAction(_))));
+    assert_eq!(errors[1].len(), 2);
+    assert!(errors[1]
+        .iter()
+        .all(|error| matches!(error.kind, SimErrorKind::StrategyPanicked { .. })));
+    assert!(errors[2].is_empty());
+    assert_eq!(errors[0][1].context.as_ref().unwrap().step, 1);
+    assert!(world
+        .inhabitants
+        .iter()
+        .all(|inhabitant| inhabitant.state.time_passed == 2));
+}
@@ -0,0 +1,226 @@
+//! Занятия, описанные в наборах содержимого, и условия их доступности.
+
+use pranaland::{
+    base_pack,
+    definitions::{EffectSource, SimErrorKind},
+    fauna::{counts, CHUCHUNDRA, SLESANDRA},
+    i18n::Locale,
+    ids::{BiomeId, LocationId},
+    locations::Location,
+    occupations::{Occupation, Prerequisite, OCCUPATIONS, SHLAMSING, ZOOMBALING},
+    resources::{amounts, HEALTH, SATISFACTION},
+    strategies::FixedActionStrategy,
+    tr, Action, ActionError, ContentPack, ContentRegistry, GameState, OccupationId,
+};
+use std::sync::Once;
+
+const MEDITATING: OccupationId = OccupationId("meditating");
+const PRANALAND: BiomeId = BiomeId("pranaland");
+
+/// Мод с занятием, доступным только в Праналенде.
+fn meditation_pack() -> ContentPack {
+    ContentPack {
+        name: "Медитация",
+        resources: vec![],
+        occupations: vec![Occupation {
+            id: MEDITATING,
+            name: "Медитировать",
+            consumes: amounts(&[(HEALTH, 0.5)]),
+            produces: SATISFACTION,
+            fauna: vec![(CHUCHUNDRA, 1.0)],
+            prerequisite: Some(Prerequisite {
+                description: "только в Праналенде",
+                check: &|_, location| location.biome == PRANALAND,
+            }),
+        }],
+        races: vec![],
+        nations: vec![],
+        biomes: vec![],
+        locations: vec![],
+        routes: vec![],
+        fauna: vec![],
+        events: vec![],
+        params: vec![],
+        messages: vec![
+            (
+                Locale::Ru,
+                "occupation.shlamsing.name = Медитативно шлямсать",
+            ),
+            (Locale::En, "occupation.meditating.name = Meditating"),
+        ],
+    }
+}
+
+static SETUP: Once = Once::new();
+
+fn setup() {
+    SETUP.call_once(|| {
+        let registry = ContentRegistry::load(base_pack(), vec![meditation_pack()]).unwrap();
+        if pranaland::install(registry).is_err() {
+            panic!("Content is already installed!");
+        }
+    });
+}
+
+fn location(id: &'static str) -> &'static Location {
+    LocationId(id).try_get().unwrap()
+}
+
+fn state_at(id: &'static str) -> GameState {
+    setup();
+    let mut state = GameState::new("Игрок".to_string(), None, 0).unwrap();
+    state.verbose = false;
+    state.location = location(id);
+    state.location_history = vec![state.location];
+    state.effective_fauna = state.world.fauna_in(state.location).unwrap();
+    state
+}
+
+fn meditate(destination: Option<&'static str>) -> Action {
+    Action {
+        destination: destination.map(location),
+        occupation: Some(MEDITATING),
+    }
+}
+
+#[test]
+fn mod_adds_an_occupation() {
+    setup();
+    let ids: Vec<OccupationId> = OCCUPATIONS.iter().map(|o| o.id).collect();
+    assert_eq!(ids.len(), 4);
+    assert_eq!(ids.last(), Some(&MEDITATING));
+    assert_eq!(Occupation::producing(HEALTH), Some(SHLAMSING));
+}
+
+#[test]
+fn mod_messages_translate_and_replace_names() {
+    setup();
+    assert_eq!(MEDITATING.name(), "Медитировать");
+    assert_eq!(SHLAMSING.name(), "Медитативно шлямсать");
+    assert_eq!(ZOOMBALING.name(), "Зумбалить");
+
+    let messages = &pranaland::content().unwrap().messages;
+    assert_eq!(
+        messages[Locale::En]["occupation.meditating.name"],
+        "Meditating"
+    );
+}
+
+#[test]
+fn prerequisite_is_checked_in_the_current_location() {
+    let state = state_at("balbesburg");
+    let error = state.validate_action(&meditate(None)).err().unwrap();
+    assert_eq!(
+        error,
+        ActionError::PrerequisiteNotMet {
+            occupation: MEDITATING,
+            requirement: "только в Праналенде",
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        tr!(
+            "error.prerequisite_not_met",
+            occupation = "Медитировать",
+            requirement = "только в Праналенде"
+        )
+    );
+
+    assert!(state_at("shrinavas")
+        .validate_action(&meditate(None))
+        .is_ok());
+}
+
+#[test]
+fn prerequisite_is_checked_in_the_destination() {
+    // Из Балбесбурга в Харе-Кириши ведёт прямая дорога паломников.
+    let state = state_at("balbesburg");
+    assert!(state
+        .validate_action(&meditate(Some("hare-kirishi")))
+        .is_ok());
+
+    // Среди допустимых действий медитация есть только там.
+    let legal = state.legal_actions();
+    let meditations: Vec<LocationId> = legal
+        .iter()
+        .filter(|action| action.occupation == Some(MEDITATING))
+        .map(|action| action.destination.unwrap().id)
+        .collect();
+    assert_eq!(meditations, vec![LocationId("hare-kirishi")]);
+}
+
+#[test]
+fn prerequisite_needs_fauna_as_well() {
+    let mut state = state_at("shrinavas");
+    state.effective_fauna = counts(&[(SLESANDRA, 1)]);
+    assert_eq!(
+        state.validate_action(&meditate(None)).err(),
+        Some(ActionError::NoFauna {
+            occupation: MEDITATING,
+            fauna: CHUCHUNDRA,
+            location: LocationId("shrinavas"),
+        })
+    );
+}
+
+#[test]
+fn met_prerequisite_lets_the_player_meditate() {
+    let mut state = state_at("shrinavas");
+    state.events = Some(Vec::new());
+    let strategy = FixedActionStrategy {
+        action: meditate(None),
+    };
+    state.advance(&strategy).unwrap();
+
+    let message = tr!(
+        "simulation.occupation",
+        name = state.player.name,
+        occupation = "Медитировать"
+    );
+    assert!(state.events.unwrap().contains(&message));
+}
+
+#[test]
+fn unknown_occupation_is_an_illegal_action() {
+    let mut state = state_at("shrinavas");
+    let unknown = OccupationId("yodeling");
+    let action = Action {
+        destination: None,
+        occupation: Some(unknown),
+    };
+    assert_eq!(
+        state.validate_action(&action).err(),
+        Some(ActionError::UnknownOccupation {
+            occupation: unknown
+        })
+    );
+
+    let error = state
+        .advance(&FixedActionStrategy { action })
+        .err()
+        .unwrap();
+    assert!(matches!(error.kind, SimErrorKind::IllegalAction(_)));
+    assert_eq!(state.time_passed, 0);
+}
+
+#[test]
+fn zoombaling_in_dolbesburg_never_brings_satisfaction() {
+    let mut state = state_at("dolbesburg");
+    let post = state.location.post_effect.unwrap();
+    let action = Action {
+        destination: None,
+        occupation: Some(ZOOMBALING),
+    };
+    state.resource_change[SATISFACTION] = 1.0;
+
+    let error = post(&mut state, &action).err().unwrap();
+    assert!(matches!(
+        error.kind,
+        SimErrorKind::WrongDirection {
+            source: EffectSource::Location(LocationId("dolbesburg")),
+            resource: SATISFACTION,
+            ..
+        }
+    ));
+    assert_eq!(error.context.unwrap().location, LocationId("dolbesburg"));
+}
@@ -0,0 +1,172 @@
+//! Параметры правил, объявленные модами.
+
+use pranaland::{
+    base_pack,
+    definitions::{EffectSource, SimErrorKind},
+    fauna::counts,
+    ids::{BiomeId, LocationId, ParamId},
+    install,
+    locations::Location,
+    params::{default_content_params, default_params, ParamDef},
+    registry::{ContentError, ContentKind},
+    resources::MONEY,
+    strategies::ApatheticStrategy,
+    world::FaunaDynamics,
+    ContentPack, ContentRegistry, GameState,
+};
+use std::sync::Once;
+
+const TITHE: ParamId = ParamId("tithe");
+const TITHEVILLE: LocationId = LocationId("titheville");
+
+/// Мод с локацией, где каждый интервал взимается десятина - параметр мода.
+fn tithe_pack() -> ContentPack {
+    ContentPack {
+        name: "Десятина",
+        resources: vec![],
+        occupations: vec![],
+        races: vec![],
+        nations: vec![],
+        biomes: vec![],
+        locations: vec![Location {
+            id: TITHEVILLE,
+            name: "Десятинск",
+            biome: BiomeId("workland"),
+            fauna: counts(&[]),
+            effect_description: "Каждый интервал забирает {tithe} денег.",
+            pre_effect: None,
+            post_effect: Some(&|state, _action| {
+                state.resource_change[MONEY] -= state.param(TITHE)?;
+                Ok(())
+            }),
+            fauna_behaviours: vec![],
+            dynamics: Some(FaunaDynamics::STANDARD),
+        }],
+        routes: vec![],
+        fauna: vec![],
+        events: vec![],
+        params: vec![ParamDef {
+            id: TITHE,
+            name: "Десятина",
+            default: 0.1,
+            probability: true,
+        }],
+        messages: vec![],
+    }
+}
+
+fn install_tithe() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let registry = ContentRegistry::load(base_pack(), vec![tithe_pack()]).unwrap();
+        assert!(install(registry).is_ok());
+    });
+}
+
+/// Игра, начинающаяся в Десятинске.
+fn game_in_titheville() -> GameState {
+    install_tithe();
+    let mut state = GameState::new("Игрок".to_string(), None, 0).unwrap();
+    state.verbose = false;
+    state.location = TITHEVILLE.try_get().unwrap();
+    state
+}
+
+/// Сколько денег за последний интервал забрал эффект Десятинска.
+fn tithe_taken(state: &GameState) -> f64 {
+    state
+        .attribution
+        .iter()
+        .find(|contribution| contribution.source == EffectSource::Location(TITHEVILLE))
+        .map(|contribution| contribution.before[MONEY] - contribution.after[MONEY])
+        .unwrap()
+}
+
+#[test]
+fn mod_params_reach_effects_and_descriptions() {
+    let mut state = game_in_titheville();
+    assert_eq!(state.param(TITHE).unwrap(), 0.1);
+    assert_eq!(
+        state
+            .location
+            .effect_description(&default_params(), &default_content_params()),
+        "Каждый интервал забирает 0.1 денег."
+    );
+
+    state.advance(&ApatheticStrategy).unwrap();
+    assert!((tithe_taken(&state) - 0.1).abs() < 1e-12);
+}
+
+#[test]
+fn changed_mod_params_change_the_rules() {
+    let mut state = game_in_titheville();
+    state.content_params[TITHE] = 0.5;
+    assert_eq!(
+        state
+            .location
+            .effect_description(&default_params(), &state.content_params),
+        "Каждый интервал забирает 0.5 денег."
+    );
+
+    state.advance(&ApatheticStrategy).unwrap();
+    assert!((tithe_taken(&state) - 0.5).abs() < 1e-12);
+}
+
+#[test]
+fn invalid_mod_param_values_are_errors() {
+    let mut state = game_in_titheville();
+    state.content_params[TITHE] = 1.5;
+
+    let error = state.advance(&ApatheticStrategy).unwrap_err();
+    assert!(
+        matches!(error.kind, SimErrorKind::InvalidContentParam { param: TITHE, value } if value == 1.5),
+        "{error}"
+    );
+}
+
+#[test]
+fn unknown_mod_params_are_errors() {
+    let state = game_in_titheville();
+
+    let error = state.param(ParamId("tax")).unwrap_err();
+    assert!(
+        matches!(&error.kind, SimErrorKind::UnknownId { kind: ContentKind::Param, id } if id == "tax"),
+        "{error}"
+    );
+}
+
+#[test]
+fn invalid_mod_param_defaults_are_reported() {
+    let mut pack = tithe_pack();
+    pack.params[0].default = 1.5;
+    pack.params.push(ParamDef {
+        id: ParamId("bribe"),
+        name: "Взятка",
+        default: -1.0,
+        probability: false,
+    });
+
+    let error = ContentRegistry::load(base_pack(), vec![pack])
+        .err()
+        .unwrap();
+    let SimErrorKind::Content(errors) = error.kind else {
+        panic!("ожидалась ошибка содержимого, получено: {error}");
+    };
+    assert_eq!(
+        errors.0,
+        vec![
+            ContentError::InvalidProbability {
+                kind: ContentKind::Param,
+                id: "tithe".to_string(),
+                field: "default",
+                value: 1.5,
+            },
+            ContentError::NegativeValue {
+                kind: ContentKind::Param,
+                id: "bribe".to_string(),
+                field: "default",
+                value: -1.0,
+            },
+        ]
+    );
+}
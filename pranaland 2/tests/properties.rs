@@ -0,0 +1,288 @@
+//! Свойства движка симуляции, проверяемые на случайном содержимом,
+//! случайных параметрах правил и случайных последовательностях действий.
+
+mod common;
+
+use common::{check, random_action, random_game, random_pack, random_state, ScriptedStrategy};
+use pranaland::{
+    definitions::{EffectSource, SimError, SimErrorKind},
+    ids::NationId,
+    locations::LOCATIONS,
+    nations::NATIONS,
+    occupations::ZOOMBALING,
+    params::{default_params, Param},
+    resources::{base_resources, MONEY, RESOURCES},
+    strategies::{FixedActionStrategy, RandomStrategy},
+    Action, ContentRegistry, GameState, Strategy,
+};
+use rand::Rng;
+use std::panic::{self, AssertUnwindSafe};
+
+const MAX_STEPS: usize = 100;
+
+/// Эффект любого вида, уже связанный с действием Игрока.
+type Effect = dyn Fn(&mut GameState) -> Result<(), SimError>;
+
+/// Проводит игру до смерти Игрока или `MAX_STEPS` интервалов и возвращает
+/// её ход: ресурсы, локацию и время после каждого интервала. Недопустимые
+/// действия стратегии пропускаются, остальные ошибки считаются нарушением.
+fn play(state: &mut GameState, strategy: &dyn Strategy) -> Result<Vec<String>, String> {
+    let mut trace = Vec::new();
+    for _ in 0..MAX_STEPS {
+        if state.player.is_dead() {
+            break;
+        }
+        match state.advance(strategy) {
+            Ok(()) => {}
+            Err(error) if error.action_error().is_some() => {}
+            Err(error) => return Err(error.to_string()),
+        }
+        trace.push(format!(
+            "{:?} {} {}",
+            state.player.resources, state.location.id, state.time_passed
+        ));
+    }
+    Ok(trace)
+}
+
+#[test]
+fn random_content_is_valid() {
+    check(
+        "случайное содержимое корректно",
+        200,
+        |rng| {
+            ContentRegistry::load(pranaland::base_pack(), vec![random_pack(rng)])
+                .map(|_| ())
+                .map_err(|error| error.to_string())
+        },
+    );
+}
+
+#[test]
+fn same_seed_same_game() {
+    check(
+        "игра определяется зерном",
+        100,
+        |rng| {
+            let state = random_game(rng);
+            let script: Vec<Action> = (0..MAX_STEPS).map(|_| random_action(rng)).collect();
+            let run = |random: bool| {
+                let mut state = state.clone();
+                if random {
+                    play(&mut state, &RandomStrategy)
+                } else {
+                    play(&mut state, &ScriptedStrategy::new(script.clone()))
+                }
+            };
+
+            for random in [false, true] {
+                if run(random)? != run(random)? {
+                    return Err(format!("ход игр разошёлся (случайная стратегия: {random})"));
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn dead_players_never_come_back() {
+    check(
+        "погибший Игрок не возвращается",
+        200,
+        |rng| {
+            let mut state = random_game(rng);
+            let strategy = ScriptedStrategy::random(rng, MAX_STEPS);
+            play(&mut state, &strategy)?;
+            if state.player.is_alive() {
+                return Ok(());
+            }
+
+            let resources = state.player.resources.clone();
+            let time_passed = state.time_passed;
+            for _ in 0..10 {
+                let action = random_action(rng);
+                if state.advance(&FixedActionStrategy { action }).is_ok() {
+                    return Err(format!(
+                        "погибший Игрок прожил интервал №{}",
+                        state.time_passed
+                    ));
+                }
+                if state.player.resources != resources || state.time_passed != time_passed {
+                    return Err(format!(
+                        "у погибшего Игрока изменились ресурсы: {:?} -> {:?}",
+                        resources, state.player.resources
+                    ));
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn idle_costs_match_the_spec() {
+    // Условие задачи: "Если дурляндец не делает ничего, он тратит по 0.5
+    // единицы каждого из жизненных показателей за интервал времени".
+    for resource in base_resources() {
+        assert_eq!(resource.idle_cost * default_params()[Param::IdleCost], 0.5);
+    }
+
+    let idle = FixedActionStrategy {
+        action: Action {
+            destination: None,
+            occupation: None,
+        },
+    };
+    check(
+        "безделье стоит столько, сколько в правилах",
+        200,
+        |rng| {
+            let mut state = random_game(rng);
+            state.advance(&idle).map_err(|error| error.to_string())?;
+
+            let change = state
+                .attribution
+                .iter()
+                .find(|contribution| contribution.source == EffectSource::Occupation)
+                .map(|contribution| contribution.delta())
+                .unwrap_or_default();
+            for resource in RESOURCES.iter() {
+                let expected = -resource.idle_cost * state.params[Param::IdleCost];
+                if (change[resource.id] - expected).abs() > 1e-9 {
+                    return Err(format!(
+                        "безделье изменило ресурс \"{}\" на {}, а не на {expected}",
+                        resource.id, change[resource.id]
+                    ));
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn zheleznoukhie_miss_takes_back_exactly_the_income() {
+    let zoombaling = FixedActionStrategy {
+        action: Action {
+            destination: None,
+            occupation: Some(ZOOMBALING),
+        },
+    };
+    check(
+        "промах Железноухого отнимает ровно доход от слесандры",
+        200,
+        |rng| {
+            let mut state = random_game(rng);
+            state.player.nationality = NationId("zheleznoukhie")
+                .try_get()
+                .map_err(|error| error.to_string())?;
+            state.params[Param::ZheleznoukhieMissChance] = 1.0;
+            state.crowding = rng.gen_range(1..5);
+            if state.validate_action(&zoombaling.action).is_err() {
+                return Ok(());
+            }
+            state
+                .advance(&zoombaling)
+                .map_err(|error| error.to_string())?;
+
+            let money = |source: EffectSource| {
+                state
+                    .attribution
+                    .iter()
+                    .filter(|contribution| contribution.source == source)
+                    .map(|contribution| contribution.delta()[MONEY])
+                    .sum::<f64>()
+            };
+            let income = money(EffectSource::Occupation);
+            let lost = money(EffectSource::Nation(NationId("zheleznoukhie")));
+            if (income + lost).abs() > 1e-9 {
+                return Err(format!("доход {income}, а потеряно {lost}"));
+            }
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn effects_tolerate_any_state() {
+    check(
+        "эффекты выдерживают любое состояние",
+        300,
+        |rng| {
+            let state = random_state(rng);
+            let action = random_action(rng);
+
+            let mut effects: Vec<(String, Box<Effect>)> = Vec::new();
+            for nation in NATIONS.iter() {
+                if let Some(pre) = nation.pre_effect {
+                    effects.push((format!("{} (до)", nation.id), Box::new(pre)));
+                }
+                if let Some(post) = nation.post_effect {
+                    effects.push((
+                        format!("{} (после)", nation.id),
+                        Box::new(move |state| post(state, &action)),
+                    ));
+                }
+            }
+            for location in LOCATIONS.iter() {
+                if let Some(pre) = location.pre_effect {
+                    effects.push((format!("{} (до)", location.id), Box::new(pre)));
+                }
+                if let Some(post) = location.post_effect {
+                    effects.push((
+                        format!("{} (после)", location.id),
+                        Box::new(move |state| post(state, &action)),
+                    ));
+                }
+                for &(fauna, behaviour) in &location.fauna_behaviours {
+                    effects.push((
+                        format!("{} ({fauna})", location.id),
+                        Box::new(move |state| {
+                            behaviour.apply(fauna, state);
+                            Ok(())
+                        }),
+                    ));
+                }
+            }
+
+            for (name, effect) in &effects {
+                let mut state = state.clone();
+                if panic::catch_unwind(AssertUnwindSafe(|| effect(&mut state))).is_err() {
+                    return Err(format!("эффект {name} запаниковал"));
+                }
+                let change = &state.resource_change;
+                if let Some(resource) = RESOURCES.iter().find(|res| !change[res.id].is_finite()) {
+                    return Err(format!(
+                        "эффект {name} изменил ресурс \"{}\" на {}",
+                        resource.id, change[resource.id]
+                    ));
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn advance_tolerates_any_state() {
+    check(
+        "интервал проходит из любого состояния",
+        300,
+        |rng| {
+            let mut state = random_state(rng);
+            state.resource_change = Default::default();
+            let strategy = FixedActionStrategy {
+                action: random_action(rng),
+            };
+
+            match panic::catch_unwind(AssertUnwindSafe(|| state.advance(&strategy))) {
+                Err(_) => Err("движок запаниковал".to_string()),
+                Ok(Err(error)) if !matches!(error.kind, SimErrorKind::IllegalAction(_)) => {
+                    Err(error.to_string())
+                }
+                Ok(_) => Ok(()),
+            }
+        },
+    );
+}
@@ -0,0 +1,13 @@
+//! Отчёты пишутся туда, куда их направит вызывающий.
+
+use pranaland::{i18n::text, reports::tabulate_information_value};
+
+#[test]
+fn reports_write_to_the_given_output() {
+    let mut out = Vec::new();
+    tabulate_information_value(&mut out).unwrap();
+
+    let report = String::from_utf8(out).unwrap();
+    assert!(report.contains(text("report.voi.information")), "{report}");
+    assert!(report.lines().count() > 2, "{report}");
+}
@@ -0,0 +1,125 @@
+//! Ресурсы, описанные в наборах содержимого.
+
+use pranaland::{
+    base_pack,
+    definitions::SimErrorKind,
+    ids::{LocationId, ResourceId},
+    registry::{ContentError, ContentKind},
+    resources::{amounts, HEALTH, MONEY},
+    travel::RouteDef,
+    ContentPack, ContentRegistry, Resource, ResourceMap,
+};
+
+const KARMA: ResourceId = ResourceId("karma");
+
+fn karma_pack() -> ContentPack {
+    ContentPack {
+        name: "Карма",
+        occupations: vec![],
+        resources: vec![Resource {
+            id: KARMA,
+            name: "Карма",
+            death_threshold: -5.0,
+            starting_amount: 0.0,
+            decay: 0.1,
+            idle_cost: 0.0,
+        }],
+        races: vec![],
+        nations: vec![],
+        biomes: vec![],
+        locations: vec![],
+        routes: vec![RouteDef {
+            from: LocationId("balbesburg"),
+            to: LocationId("shrinavas"),
+            cost: amounts(&[(KARMA, 1.0)]),
+            extra_intervals: 0,
+        }],
+        fauna: vec![],
+        events: vec![],
+        params: vec![],
+        messages: vec![],
+    }
+}
+
+#[test]
+fn mod_adds_a_resource() {
+    let registry = ContentRegistry::load(base_pack(), vec![karma_pack()]).unwrap();
+
+    let ids: Vec<ResourceId> = registry.resources.iter().map(|res| res.id).collect();
+    assert_eq!(ids.len(), 4);
+    assert_eq!(ids.last(), Some(&KARMA));
+    assert_eq!(registry.resources[3].death_threshold, -5.0);
+}
+
+#[test]
+fn unknown_resource_is_reported() {
+    let mut pack = karma_pack();
+    pack.resources.clear();
+
+    let error = ContentRegistry::load(base_pack(), vec![pack])
+        .err()
+        .unwrap();
+    let SimErrorKind::Content(errors) = error.kind else {
+        panic!("ожидалась ошибка содержимого, получено: {error}");
+    };
+    assert_eq!(
+        errors.0,
+        vec![ContentError::DanglingReference {
+            kind: ContentKind::Route,
+            id: "balbesburg -> shrinavas".to_string(),
+            target_kind: ContentKind::Resource,
+            target: "karma".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn resource_maps_compare_all_resources() {
+    // Незаписанный ресурс равен нулю, поэтому явный ноль ничего не меняет.
+    let mut explicit = amounts(&[(HEALTH, 1.0)]);
+    explicit[MONEY] = 0.0;
+    assert_eq!(explicit, amounts(&[(HEALTH, 1.0)]));
+    assert_ne!(explicit, amounts(&[(HEALTH, 1.0), (KARMA, 1.0)]));
+
+    // Множители по умолчанию - единицы, и с нулями они не совпадают.
+    assert_ne!(ResourceMap::filled(1.0), ResourceMap::default());
+    assert_eq!(ResourceMap::filled(1.0)[KARMA], 1.0);
+}
+
+#[test]
+fn negative_costs_and_shares_are_reported() {
+    let mut pack = karma_pack();
+    pack.resources[0].decay = 1.5;
+    pack.resources[0].idle_cost = -1.0;
+    pack.routes[0].cost = amounts(&[(KARMA, -1.0)]);
+
+    let error = ContentRegistry::load(base_pack(), vec![pack])
+        .err()
+        .unwrap();
+    let SimErrorKind::Content(errors) = error.kind else {
+        panic!("ожидалась ошибка содержимого, получено: {error}");
+    };
+    assert_eq!(
+        errors.0,
+        vec![
+            ContentError::InvalidProbability {
+                kind: ContentKind::Resource,
+                id: "karma".to_string(),
+                field: "decay",
+                value: 1.5,
+            },
+            ContentError::NegativeValue {
+                kind: ContentKind::Resource,
+                id: "karma".to_string(),
+                field: "idle_cost",
+                value: -1.0,
+            },
+            ContentError::NegativeValue {
+                kind: ContentKind::Route,
+                id: "balbesburg -> shrinavas [karma]".to_string(),
+                field: "cost",
+                value: -1.0,
+            },
+        ]
+    );
+}
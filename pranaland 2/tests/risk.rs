@@ -0,0 +1,178 @@
+//! Меры риска на маленьких пакетах, посчитанных вручную.
+
+use pranaland::{
+    batch::{BatchResult, GameOutcome},
+    nations::NATIONS,
+    risk::{certainty_equivalent, cvar_of_min_resource, probability_of_ruin, Objective},
+};
+use std::collections::HashMap;
+
+const EPSILON: f64 = 1e-9;
+
+fn outcome(steps_survived: usize, min_resource_trace: Vec<f64>) -> GameOutcome {
+    GameOutcome {
+        nation: &NATIONS[0],
+        steps_survived,
+        survived: false,
+        min_resource_trace,
+        death_cause: None,
+        losses: HashMap::new(),
+        fatal_effects: Vec::new(),
+        failed: false,
+    }
+}
+
+/// Три симуляции: прожившие 2, 4 и 10 интервалов,
+/// с минимумами ресурсов 1.0, 0.5 и 6.0 соответственно.
+fn batch() -> BatchResult {
+    batch_with_offset(0)
+}
+
+/// Тот же пакет, но каждая симуляция прожила на `offset` интервалов дольше.
+fn batch_with_offset(offset: usize) -> BatchResult {
+    BatchResult {
+        outcomes: vec![
+            outcome(2 + offset, vec![5.0, 1.0]),
+            outcome(4 + offset, vec![3.0, 2.0, 0.5, 4.0]),
+            outcome(
+                10 + offset,
+                vec![9.0, 8.0, 7.0, 6.0, 6.5, 7.0, 8.0, 9.0, 9.5, 10.0],
+            ),
+        ],
+        errors: Vec::new(),
+    }
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < EPSILON,
+        "ожидалось {expected}, получено {actual}"
+    );
+}
+
+#[test]
+fn probability_of_ruin_counts_only_the_horizon() {
+    let result = batch();
+
+    // За 2 интервала до 1.0 опускается только первая симуляция.
+    assert_close(probability_of_ruin(&result, 1.0, 2), 1.0 / 3.0);
+    // На третьем интервале вторая симуляция опускается до 0.5.
+    assert_close(probability_of_ruin(&result, 1.0, 3), 2.0 / 3.0);
+    // Порог включается: 0.5 <= 0.5.
+    assert_close(probability_of_ruin(&result, 0.5, 10), 1.0 / 3.0);
+    assert_close(probability_of_ruin(&result, 0.0, 10), 0.0);
+    assert_close(probability_of_ruin(&result, 6.0, 10), 1.0);
+    assert_close(probability_of_ruin(&result, 1.0, 0), 0.0);
+}
+
+#[test]
+fn cvar_averages_the_worst_tail() {
+    let result = batch();
+
+    // Отсортированные минимумы: 0.5, 1.0, 6.0.
+    // alpha = 0.5: ceil(1.5) = 2 худшие симуляции.
+    assert_close(cvar_of_min_resource(&result, 0.5), (0.5 + 1.0) / 2.0);
+    // Хвост не бывает пустым.
+    assert_close(cvar_of_min_resource(&result, 0.01), 0.5);
+    assert_close(cvar_of_min_resource(&result, 0.0), 0.5);
+    assert_close(cvar_of_min_resource(&result, 1.0), (0.5 + 1.0 + 6.0) / 3.0);
+}
+
+#[test]
+fn certainty_equivalent_with_zero_lambda_is_the_mean() {
+    let result = batch();
+
+    assert_close(result.mean_survival(), 16.0 / 3.0);
+    assert_close(certainty_equivalent(&result, 0.0), 16.0 / 3.0);
+}
+
+#[test]
+fn certainty_equivalent_matches_hand_computation() {
+    let result = batch();
+
+    // При lambda = ln 2 полезность равна -2^(-x). После сдвига на минимум (2):
+    // E[2^-(x - 2)] = (1 + 2^-2 + 2^-8) / 3, а детерминированный эквивалент
+    // равен 2 - log2 этого среднего.
+    let expected_utility: f64 = (1.0 + 0.25 + 1.0 / 256.0) / 3.0;
+    let expected = 2.0 - expected_utility.log2();
+    assert_close(certainty_equivalent(&result, 2f64.ln()), expected);
+
+    // Не склонный к риску Игрок оценивает лотерею ниже среднего,
+    // но не ниже худшего исхода.
+    assert!(expected < result.mean_survival());
+    assert!(expected > 2.0);
+}
+
+#[test]
+fn certainty_equivalent_shift_does_not_overflow() {
+    // Без сдвига на минимум exp(-lambda * x) при x около 100 000 обращается в ноль,
+    // и логарифм даёт бесконечность.
+    const OFFSET: usize = 100_000;
+
+    let lambda = 2f64.ln();
+    let shifted = certainty_equivalent(&batch_with_offset(OFFSET), lambda);
+
+    assert!(shifted.is_finite());
+    assert_close(
+        shifted,
+        certainty_equivalent(&batch(), lambda) + OFFSET as f64,
+    );
+}
+
+#[test]
+fn certainty_equivalent_for_risk_seeking_player() {
+    let result = batch();
+
+    // При отрицательной lambda сдвиг идёт на максимум (10):
+    // E[2^(x - 10)] = (2^-8 + 2^-6 + 1) / 3, и оценка выше среднего.
+    let expected_utility: f64 = (1.0 / 256.0 + 1.0 / 64.0 + 1.0) / 3.0;
+    let expected = 10.0 + expected_utility.log2();
+    let actual = certainty_equivalent(&result, -2f64.ln());
+
+    assert_close(actual, expected);
+    assert!(actual > result.mean_survival());
+    assert!(actual < 10.0);
+}
+
+#[test]
+fn risk_seeking_certainty_equivalent_does_not_overflow() {
+    // Сдвиг на минимум дал бы exp(1000 * ln 2) = бесконечность.
+    let result = BatchResult {
+        outcomes: vec![outcome(0, vec![1.0]), outcome(1000, vec![1.0])],
+        errors: Vec::new(),
+    };
+
+    // E[2^(x - 1000)] = (2^-1000 + 1) / 2, что в f64 равно 1/2.
+    assert_close(certainty_equivalent(&result, -2f64.ln()), 999.0);
+}
+
+#[test]
+fn objectives_score_higher_for_better_strategies() {
+    let result = batch();
+
+    assert_close(
+        Objective::ProbabilityOfRuin {
+            threshold: 1.0,
+            horizon: 3,
+        }
+        .score(&result),
+        -2.0 / 3.0,
+    );
+    assert_close(Objective::CVaR { alpha: 0.5 }.score(&result), 0.75);
+    assert_close(
+        Objective::RiskAverse { lambda: 0.0 }.score(&result),
+        16.0 / 3.0,
+    );
+    assert_close(Objective::MeanSurvival.score(&result), 16.0 / 3.0);
+}
+
+#[test]
+fn empty_batch_has_no_risk() {
+    let result = BatchResult {
+        outcomes: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    assert_close(probability_of_ruin(&result, 1.0, 10), 0.0);
+    assert_close(cvar_of_min_resource(&result, 0.5), 0.0);
+}
@@ -0,0 +1,145 @@
+//! Оценка Каплана-Мейера на маленьком пакете, посчитанном вручную.
+
+use pranaland::{
+    batch::GameOutcome,
+    nations::NATIONS,
+    resources::{HEALTH, MONEY, SATISFACTION},
+    survival::{write_csv, write_death_causes_csv, SurvivalCurve},
+    ResourceId,
+};
+use std::{collections::HashMap, fs};
+
+const EPSILON: f64 = 1e-9;
+const HORIZON: usize = 5;
+
+fn outcome(steps_survived: usize, death_cause: Option<ResourceId>) -> GameOutcome {
+    GameOutcome {
+        nation: &NATIONS[0],
+        steps_survived,
+        survived: death_cause.is_none(),
+        min_resource_trace: Vec::new(),
+        death_cause,
+        losses: HashMap::new(),
+        fatal_effects: Vec::new(),
+        failed: false,
+    }
+}
+
+/// Семь Игроков: погибшие на 1-м, 3-м, 3-м и 5-м интервалах, один выбывший
+/// живым после 2-го интервала и двое, доживших до горизонта.
+fn curve() -> SurvivalCurve {
+    let outcomes = [
+        outcome(1, Some(HEALTH)),
+        outcome(2, None),
+        outcome(3, Some(MONEY)),
+        outcome(3, Some(HEALTH)),
+        outcome(5, Some(HEALTH)),
+        outcome(HORIZON, None),
+        outcome(HORIZON, None),
+    ];
+    SurvivalCurve::new("нация", "стратегия", &outcomes, HORIZON)
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < EPSILON,
+        "ожидалось {expected}, получено {actual}"
+    );
+}
+
+#[test]
+fn kaplan_meier_matches_hand_computation() {
+    let curve = curve();
+
+    // (интервал, под риском, погибло, цензурировано, выживаемость)
+    let expected = [
+        (1, 7, 1, 0, 6.0 / 7.0),
+        (2, 6, 0, 1, 6.0 / 7.0),
+        (3, 5, 2, 0, 6.0 / 7.0 * 3.0 / 5.0),
+        (4, 3, 0, 0, 18.0 / 35.0),
+        (5, 3, 1, 2, 18.0 / 35.0 * 2.0 / 3.0),
+    ];
+
+    assert_eq!(curve.points.len(), expected.len());
+    for (point, (step, at_risk, deaths, censored, survival)) in curve.points.iter().zip(expected) {
+        assert_eq!(point.step, step);
+        assert_eq!(point.at_risk, at_risk, "интервал {step}");
+        assert_eq!(point.deaths, deaths, "интервал {step}");
+        assert_eq!(point.censored, censored, "интервал {step}");
+        assert_close(point.survival, survival);
+        assert_close(point.hazard, deaths as f64 / at_risk as f64);
+    }
+
+    // Цензурирование не считается гибелью: доля просто погибших дала бы 3/7.
+    assert_close(curve.final_survival(), 12.0 / 35.0);
+}
+
+#[test]
+fn death_causes_are_counted_per_resource() {
+    let curve = curve();
+
+    assert_eq!(curve.death_causes[HEALTH], 3);
+    assert_eq!(curve.death_causes[MONEY], 1);
+    assert_eq!(curve.death_causes[SATISFACTION], 0);
+}
+
+#[test]
+fn everyone_censored_at_horizon_keeps_full_survival() {
+    let outcomes: Vec<_> = (0..4).map(|_| outcome(HORIZON, None)).collect();
+    let curve = SurvivalCurve::new("нация", "стратегия", &outcomes, HORIZON);
+
+    assert!(curve
+        .points
+        .iter()
+        .all(|p| p.survival == 1.0 && p.deaths == 0));
+    assert_eq!(curve.points[HORIZON - 1].censored, 4);
+    assert_eq!(curve.points[HORIZON - 1].at_risk, 4);
+}
+
+#[test]
+fn empty_batch_has_full_survival() {
+    let curve = SurvivalCurve::new("нация", "стратегия", &[], HORIZON);
+
+    assert_eq!(curve.points.len(), HORIZON);
+    assert_close(curve.final_survival(), 1.0);
+}
+
+#[test]
+fn deaths_after_horizon_are_censored() {
+    let outcomes = [outcome(HORIZON + 3, Some(HEALTH)), outcome(2, Some(MONEY))];
+    let curve = SurvivalCurve::new("нация", "стратегия", &outcomes, HORIZON);
+
+    let last = curve.points[HORIZON - 1];
+    assert_eq!((last.deaths, last.censored), (0, 1));
+    assert_eq!(curve.death_causes[HEALTH], 0);
+    assert_eq!(curve.death_causes[MONEY], 1);
+    assert_close(curve.final_survival(), 0.5);
+}
+
+#[test]
+fn csv_quotes_names_with_commas_and_quotes() {
+    let curves = [SurvivalCurve::new(
+        "Можоры, \"элита\"",
+        "Стратегия, жадная",
+        &[outcome(1, Some(HEALTH))],
+        1,
+    )];
+    let dir = std::env::temp_dir().join(format!("pranaland-survival-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    write_csv(&curves, &dir.join("survival.csv")).unwrap();
+    write_death_causes_csv(&curves, &dir.join("death_causes.csv")).unwrap();
+    let survival = fs::read_to_string(dir.join("survival.csv")).unwrap();
+    let causes = fs::read_to_string(dir.join("death_causes.csv")).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let prefix = "\"Можоры, \"\"элита\"\"\",\"Стратегия, жадная\",";
+    assert!(
+        survival.lines().nth(1).unwrap().starts_with(prefix),
+        "{survival}"
+    );
+    assert!(
+        causes.lines().skip(1).all(|line| line.starts_with(prefix)),
+        "{causes}"
+    );
+}
@@ -0,0 +1,406 @@
+//! Биржевая книга: встречные предложения, цены, частичное исполнение
+//! и отказы, о которых стратегия узнаёт на следующих торгах.
+
+use pranaland::{
+    definitions::{Action, GameState, Strategy},
+    ids::{LocationId, ResourceId},
+    locations::Location,
+    resources::{HEALTH, MONEY},
+    strategies::ApatheticStrategy,
+    trade::{MarketView, Offer, Rejection, TradeAction, TradeError},
+    World,
+};
+use std::{cell::RefCell, rc::Rc};
+
+const EPSILON: f64 = 1e-9;
+
+fn market_place() -> &'static Location {
+    LocationId("dolbesburg").try_get().unwrap()
+}
+
+/// Мир из `n` жителей, стоящих в одной локации, по 10 единиц каждого ресурса.
+fn world(n: usize) -> World {
+    let mut world = World::new(3);
+    for i in 0..n {
+        world
+            .add_inhabitant(
+                format!("Житель №{}", i + 1),
+                None,
+                Box::new(ApatheticStrategy),
+            )
+            .unwrap();
+    }
+    for inhabitant in &mut world.inhabitants {
+        inhabitant.state.location = market_place();
+        inhabitant.state.location_history = vec![market_place()];
+    }
+    world
+}
+
+fn offers(world: &World) -> Vec<Offer> {
+    world.market.books[market_place().index().unwrap()]
+        .offers
+        .clone()
+}
+
+fn resource(world: &World, who: usize, resource: ResourceId) -> f64 {
+    world.inhabitants[who].state.player.resources[resource]
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < EPSILON,
+        "ожидалось {expected}, получено {actual}"
+    );
+}
+
+/// Житель 0 продаёт 4 денег по 2 единицы здоровья за каждую.
+fn sell_money(world: &mut World) {
+    world
+        .perform_trade(
+            0,
+            market_place(),
+            TradeAction::Offer {
+                sell: MONEY,
+                amount: 4.0,
+                buy: HEALTH,
+                price: 2.0,
+            },
+        )
+        .unwrap();
+}
+
+/// Житель `who` продаёт `amount` здоровья по `price` денег за единицу.
+fn sell_health(world: &mut World, who: usize, amount: f64, price: f64) -> Result<(), TradeError> {
+    world.perform_trade(
+        who,
+        market_place(),
+        TradeAction::Offer {
+            sell: HEALTH,
+            amount,
+            buy: MONEY,
+            price,
+        },
+    )
+}
+
+#[test]
+fn unmatched_offer_waits_in_the_book() {
+    let mut world = world(2);
+    sell_money(&mut world);
+
+    let book = offers(&world);
+    assert_eq!(book.len(), 1);
+    assert_eq!(book[0].owner, 0);
+    assert_close(book[0].amount, 4.0);
+    assert!(world.market.trades.is_empty());
+    // Ресурсы не резервируются, пока предложение не принято.
+    assert_close(resource(&world, 0, MONEY), 10.0);
+}
+
+#[test]
+fn accept_fills_partially_at_offer_price() {
+    let mut world = world(2);
+    sell_money(&mut world);
+    let id = offers(&world)[0].id;
+
+    world
+        .perform_trade(
+            1,
+            market_place(),
+            TradeAction::Accept {
+                offer: id,
+                amount: 1.0,
+            },
+        )
+        .unwrap();
+    assert_close(resource(&world, 0, MONEY), 9.0);
+    assert_close(resource(&world, 0, HEALTH), 12.0);
+    assert_close(resource(&world, 1, MONEY), 11.0);
+    assert_close(resource(&world, 1, HEALTH), 8.0);
+    assert_close(offers(&world)[0].amount, 3.0);
+
+    // Больше, чем осталось, не продаётся: предложение исполняется до конца.
+    world
+        .perform_trade(
+            1,
+            market_place(),
+            TradeAction::Accept {
+                offer: id,
+                amount: 100.0,
+            },
+        )
+        .unwrap();
+    assert_close(resource(&world, 1, MONEY), 14.0);
+    assert_close(resource(&world, 1, HEALTH), 2.0);
+    assert!(offers(&world).is_empty());
+
+    let prices: Vec<f64> = world
+        .market
+        .trades
+        .iter()
+        .map(|trade| trade.price)
+        .collect();
+    assert_eq!(prices, [2.0, 2.0]);
+    assert_eq!(
+        world
+            .market
+            .last_price(market_place(), MONEY, HEALTH),
+        Some(2.0)
+    );
+}
+
+#[test]
+fn crossing_offers_match_at_resting_price() {
+    let mut world = world(2);
+    sell_money(&mut world);
+
+    // Житель 1 согласен на 0.5 денег за единицу здоровья, то есть на 2 здоровья
+    // за единицу денег - ровно столько, сколько просит житель 0.
+    sell_health(&mut world, 1, 6.0, 0.5).unwrap();
+
+    let book = offers(&world);
+    assert_eq!(book.len(), 1, "{book:?}");
+    assert_eq!(book[0].owner, 0);
+    assert_close(book[0].amount, 1.0);
+
+    let trade = world.market.trades[0];
+    assert_eq!((trade.seller, trade.buyer), (0, 1));
+    assert_close(trade.amount, 3.0);
+    assert_close(trade.price, 2.0);
+    assert_close(resource(&world, 1, HEALTH), 4.0);
+    assert_close(resource(&world, 1, MONEY), 13.0);
+}
+
+#[test]
+fn partially_matched_offer_rests_with_the_remainder() {
+    let mut world = world(2);
+    sell_money(&mut world);
+
+    // За 8 здоровья житель 1 выкупает все 4 единицы денег,
+    // а оставшиеся 2 здоровья выставляет на рынок по своей цене.
+    sell_health(&mut world, 1, 9.0, 0.4).unwrap();
+
+    let book = offers(&world);
+    assert_eq!(book.len(), 1, "{book:?}");
+    assert_eq!(book[0].owner, 1);
+    assert_eq!(book[0].sell, HEALTH);
+    assert_close(book[0].amount, 1.0);
+    assert_close(book[0].price, 0.4);
+    assert_close(resource(&world, 1, HEALTH), 2.0);
+    assert_close(resource(&world, 1, MONEY), 14.0);
+}
+
+#[test]
+fn offers_do_not_match_when_prices_do_not_cross() {
+    let mut world = world(2);
+    sell_money(&mut world);
+    sell_health(&mut world, 1, 6.0, 1.0).unwrap();
+
+    assert_eq!(offers(&world).len(), 2);
+    assert!(world.market.trades.is_empty());
+}
+
+#[test]
+fn best_offer_is_the_cheapest_foreign_one() {
+    let mut world = world(3);
+    sell_health(&mut world, 0, 2.0, 3.0).unwrap();
+    sell_health(&mut world, 1, 2.0, 1.5).unwrap();
+    sell_health(&mut world, 2, 2.0, 0.5).unwrap();
+
+    let book = &world.market.books[market_place().index().unwrap()];
+    let best = |me| {
+        book.best_offer(HEALTH, MONEY, me)
+            .map(|offer| offer.owner)
+    };
+    assert_eq!(best(0), Some(2));
+    assert_eq!(best(2), Some(1));
+    assert_eq!(
+        book.best_offer(MONEY, HEALTH, 0)
+            .map(|o| o.owner),
+        None
+    );
+}
+
+#[test]
+fn non_finite_amounts_and_prices_are_rejected() {
+    let mut world = world(2);
+    let place = market_place();
+    sell_money(&mut world);
+    let id = offers(&world)[0].id;
+
+    for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        assert_eq!(
+            sell_health(&mut world, 1, 1.0, value),
+            Err(TradeError::NonPositive)
+        );
+        assert_eq!(
+            sell_health(&mut world, 1, value, 1.0),
+            Err(TradeError::NonPositive)
+        );
+        assert_eq!(
+            world.perform_trade(
+                1,
+                place,
+                TradeAction::Accept {
+                    offer: id,
+                    amount: value
+                }
+            ),
+            Err(TradeError::NonPositive)
+        );
+    }
+
+    assert_eq!(offers(&world).len(), 1);
+    assert!(world.market.trades.is_empty());
+    for who in 0..2 {
+        assert_eq!(resource(&world, who, MONEY), 10.0);
+        assert_eq!(resource(&world, who, HEALTH), 10.0);
+    }
+}
+
+#[test]
+fn invalid_trades_are_rejected() {
+    let mut world = world(2);
+    let place = market_place();
+    sell_money(&mut world);
+    let id = offers(&world)[0].id;
+
+    let offer = |sell, amount, buy, price| TradeAction::Offer {
+        sell,
+        amount,
+        buy,
+        price,
+    };
+    assert_eq!(
+        world.perform_trade(1, place, offer(MONEY, 1.0, MONEY, 1.0)),
+        Err(TradeError::SameResource)
+    );
+    assert_eq!(
+        world.perform_trade(1, place, offer(MONEY, 0.0, HEALTH, 1.0)),
+        Err(TradeError::NonPositive)
+    );
+    assert_eq!(
+        world.perform_trade(
+            0,
+            place,
+            TradeAction::Accept {
+                offer: id,
+                amount: 1.0
+            }
+        ),
+        Err(TradeError::OwnOffer)
+    );
+    assert_eq!(
+        world.perform_trade(1, place, TradeAction::Cancel { offer: id }),
+        Err(TradeError::UnknownOffer(id))
+    );
+    assert_eq!(
+        world.perform_trade(
+            1,
+            place,
+            offer(MONEY, 10.0, HEALTH, 1.0)
+        ),
+        Err(TradeError::Insufficient {
+            resource: MONEY,
+            required: 10.0,
+            available: 10.0,
+        })
+    );
+    // Покупатель не может заплатить 2 * 4 = 8 здоровья из 5.
+    world.inhabitants[1].state.player.resources[HEALTH] = 5.0;
+    assert!(matches!(
+        world.perform_trade(
+            1,
+            place,
+            TradeAction::Accept {
+                offer: id,
+                amount: 5.0
+            }
+        ),
+        Err(TradeError::Insufficient {
+            resource: HEALTH,
+            ..
+        })
+    ));
+    assert!(world.market.trades.is_empty());
+
+    world
+        .perform_trade(0, place, TradeAction::Cancel { offer: id })
+        .unwrap();
+    assert!(offers(&world).is_empty());
+}
+
+/// Стратегия, которая каждый раз принимает несуществующее предложение
+/// и запоминает, какие отказы видела.
+struct Stubborn {
+    seen: Rc<RefCell<Vec<Vec<Rejection>>>>,
+}
+
+impl Strategy for Stubborn {
+    fn name(&self) -> &'static str {
+        "Упрямая стратегия"
+    }
+
+    fn take_action(&self, _state: &mut GameState) -> Action {
+        Action {
+            destination: None,
+            occupation: None,
+        }
+    }
+
+    fn trade(&self, _state: &GameState, market: &MarketView) -> Vec<TradeAction> {
+        self.seen.borrow_mut().push(market.rejected.to_vec());
+        vec![TradeAction::Accept {
+            offer: 999,
+            amount: 1.0,
+        }]
+    }
+}
+
+#[test]
+fn there_is_no_market_outside_the_content() {
+    let atlantis: &'static Location = Box::leak(Box::new(Location {
+        id: LocationId("atlantis"),
+        name: "Атлантида",
+        biome: market_place().biome,
+        fauna: market_place().fauna.clone(),
+        effect_description: "",
+        pre_effect: None,
+        post_effect: None,
+        fauna_behaviours: vec![],
+        dynamics: None,
+    }));
+    let mut world = world(1);
+
+    let result = world.perform_trade(0, atlantis, TradeAction::Cancel { offer: 0 });
+    assert_eq!(result, Err(TradeError::NoMarket(LocationId("atlantis"))));
+}
+
+#[test]
+fn rejected_trades_are_reported_on_the_next_interval() {
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let mut world = World::new(5);
+    world
+        .add_inhabitant(
+            "Упрямец".to_string(),
+            None,
+            Box::new(Stubborn { seen: seen.clone() }),
+        )
+        .unwrap();
+
+    world.advance().unwrap();
+    world.advance().unwrap();
+
+    let seen = seen.borrow();
+    assert_eq!(seen.len(), 2);
+    assert!(seen[0].is_empty());
+    assert_eq!(seen[1].len(), 1);
+    assert!(matches!(
+        seen[1][0],
+        (
+            TradeAction::Accept { offer: 999, .. },
+            TradeError::UnknownOffer(999)
+        )
+    ));
+}
@@ -0,0 +1,254 @@
+//! Маршруты базового содержимого: поиск пути и допустимые направления.
+
+use pranaland::{
+    ids::LocationId,
+    locations::{Location, LOCATIONS},
+    occupations::SHLAMSING,
+    strategies::FixedActionStrategy,
+    tr,
+    travel::{find_route, next_hop, routes_from},
+    Action, ActionError, GameState,
+};
+use std::collections::{HashSet, VecDeque};
+
+/// Локация, которой нет в содержимом игры.
+fn atlantis() -> &'static Location {
+    Box::leak(Box::new(Location {
+        id: LocationId("atlantis"),
+        name: "Атлантида",
+        biome: location("balbesburg").biome,
+        fauna: location("balbesburg").fauna.clone(),
+        effect_description: "",
+        pre_effect: None,
+        post_effect: None,
+        fauna_behaviours: vec![],
+        dynamics: None,
+    }))
+}
+
+fn location(id: &'static str) -> &'static Location {
+    LocationId(id).try_get().unwrap()
+}
+
+/// Игра, в которой Игрок стоит в локации `id`.
+fn state_at(id: &'static str) -> GameState {
+    let mut state = GameState::new("Игрок".to_string(), None, 0).unwrap();
+    state.verbose = false;
+    state.location = location(id);
+    state.location_history = vec![state.location];
+    state.effective_fauna = state.world.fauna_in(state.location).unwrap();
+    state
+}
+
+/// Идентификатор первой локации на кратчайшем пути из `from` в `to`.
+fn hop(from: &'static str, to: &'static str) -> Option<LocationId> {
+    next_hop(location(from), location(to)).map(|loc| loc.id)
+}
+
+/// Число переходов на кратчайшем пути, посчитанное независимо от `next_hop`.
+fn distance(from: &'static Location, to: &'static Location) -> Option<usize> {
+    let mut seen = HashSet::from([from.id]);
+    let mut queue = VecDeque::from([(from, 0)]);
+    while let Some((current, hops)) = queue.pop_front() {
+        if current == to {
+            return Some(hops);
+        }
+        for route in routes_from(current) {
+            if seen.insert(route.to.id) {
+                queue.push_back((route.to, hops + 1));
+            }
+        }
+    }
+    None
+}
+
+#[test]
+fn next_hop_on_known_paths() {
+    assert_eq!(hop("balbesburg", "balbesburg"), None);
+    // Соседи по биому и прямой маршрут паломников.
+    assert_eq!(
+        hop("balbesburg", "dolbesburg"),
+        Some(LocationId("dolbesburg"))
+    );
+    assert_eq!(
+        hop("balbesburg", "hare-kirishi"),
+        Some(LocationId("hare-kirishi"))
+    );
+    // В Шринавас быстрее всего через Харе-Кириши.
+    assert_eq!(
+        hop("balbesburg", "shrinavas"),
+        Some(LocationId("hare-kirishi"))
+    );
+    // Обратно из Праналенда - только через Бичленд.
+    assert_eq!(
+        hop("hare-kirishi", "balbesburg"),
+        Some(LocationId("shrinavas"))
+    );
+    assert_eq!(
+        hop("hare-kirishi", "kuramariby"),
+        Some(LocationId("shrinavas"))
+    );
+}
+
+#[test]
+fn next_hop_follows_shortest_paths() {
+    for from in LOCATIONS.iter() {
+        for to in LOCATIONS.iter() {
+            let Some(hops) = distance(from, to).filter(|&hops| hops > 0) else {
+                assert!(next_hop(from, to).is_none(), "{} -> {}", from.id, to.id);
+                continue;
+            };
+
+            let hop = next_hop(from, to).unwrap_or_else(|| panic!("{} -> {}", from.id, to.id));
+            assert!(find_route(from, hop).is_some(), "{} -> {}", from.id, hop.id);
+            assert_eq!(
+                distance(hop, to),
+                Some(hops - 1),
+                "{} -> {} через {}",
+                from.id,
+                to.id,
+                hop.id
+            );
+        }
+    }
+}
+
+#[test]
+fn one_way_route_is_not_usable_in_reverse() {
+    assert!(find_route(location("balbesburg"), location("hare-kirishi")).is_some());
+    assert!(find_route(location("hare-kirishi"), location("balbesburg")).is_none());
+
+    let state = state_at("hare-kirishi");
+    assert!(!state.legal_destinations().contains(&location("balbesburg")));
+
+    let back = Action {
+        destination: Some(location("balbesburg")),
+        occupation: None,
+    };
+    assert_eq!(
+        state.validate_action(&back).err(),
+        Some(ActionError::NoRoute {
+            from: LocationId("hare-kirishi"),
+            to: LocationId("balbesburg"),
+        })
+    );
+
+    let there = Action {
+        destination: Some(location("hare-kirishi")),
+        occupation: None,
+    };
+    assert!(state_at("balbesburg").validate_action(&there).is_ok());
+}
+
+#[test]
+fn legal_destinations_match_routes() {
+    for here in LOCATIONS.iter() {
+        let state = state_at(here.id.0);
+        let destinations: HashSet<_> = state
+            .legal_destinations()
+            .iter()
+            .map(|loc| loc.id)
+            .collect();
+        let expected: HashSet<_> = std::iter::once(here.id)
+            .chain(routes_from(here).map(|route| route.to.id))
+            .collect();
+        assert_eq!(destinations, expected, "{}", here.id);
+
+        for there in LOCATIONS.iter() {
+            let action = Action {
+                destination: Some(there),
+                occupation: None,
+            };
+            let no_route = matches!(
+                state.validate_action(&action),
+                Err(ActionError::NoRoute { .. })
+            );
+            assert_eq!(
+                no_route,
+                !destinations.contains(&there.id),
+                "{} -> {}",
+                here.id,
+                there.id
+            );
+        }
+    }
+}
+
+/// Паломник отправляется шлямсать в Харе-Кириши. Пока он в пути,
+/// вся фауна там исчезает, если `deplete`. Возвращает журнал прибытия.
+fn arrive_at_hare_kirishi(deplete: bool) -> (GameState, Vec<String>) {
+    let strategy = FixedActionStrategy {
+        action: Action {
+            destination: Some(location("hare-kirishi")),
+            occupation: Some(SHLAMSING),
+        },
+    };
+    let mut state = state_at("balbesburg");
+    state.advance(&strategy).unwrap();
+    assert!(state.transit.is_some());
+
+    if deplete {
+        let hare_kirishi = location("hare-kirishi").index().unwrap();
+        for (_, population) in state.world.fauna[hare_kirishi].iter_mut() {
+            *population = 0.0;
+        }
+    }
+
+    state.events = Some(Vec::new());
+    state.advance(&strategy).unwrap();
+    assert_eq!(state.location.id, LocationId("hare-kirishi"));
+    let events = state.events.take().unwrap();
+    (state, events)
+}
+
+#[test]
+fn occupation_is_revalidated_on_arrival() {
+    let worked = |state: &GameState, events: &[String]| {
+        let message = tr!(
+            "simulation.occupation",
+            name = state.player.name,
+            occupation = SHLAMSING.name()
+        );
+        events.contains(&message)
+    };
+
+    let (state, events) = arrive_at_hare_kirishi(false);
+    assert!(
+        !events.iter().any(|event| event.contains("По прибытии")),
+        "{events:#?}"
+    );
+    assert!(worked(&state, &events));
+
+    let (state, events) = arrive_at_hare_kirishi(true);
+    assert!(
+        events.iter().any(|event| event.contains("По прибытии")),
+        "{events:#?}"
+    );
+    assert!(state.effective_fauna.values().all(|&count| count == 0));
+    assert!(!worked(&state, &events));
+}
+
+#[test]
+fn locations_outside_the_content_are_errors() {
+    let atlantis = atlantis();
+    let state = state_at("balbesburg");
+
+    assert!(atlantis.index().is_err());
+    assert!(state.world.fauna_in(atlantis).is_err());
+    assert!(next_hop(location("balbesburg"), atlantis).is_none());
+    assert!(next_hop(atlantis, location("balbesburg")).is_none());
+    let error = state
+        .validate_action(&Action {
+            destination: Some(atlantis),
+            occupation: None,
+        })
+        .err()
+        .unwrap();
+    assert_eq!(
+        error,
+        ActionError::NoRoute {
+            from: LocationId("balbesburg"),
+            to: LocationId("atlantis"),
+        }
+    );
+}